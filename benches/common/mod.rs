@@ -0,0 +1,47 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   mod.rs                                             :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:39:16 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:39:16 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// Shared by the benchmarks and src/test.rs
+
+use std::time::{Duration, Instant};
+
+// Deterministic values in [-0.5, 0.5) from a 32-bit LCG
+
+pub fn fill(len: usize, seed: u32) -> Vec<f32> {
+	let mut state = seed;
+	let mut result = Vec::with_capacity(len);
+
+	for _ in 0..len {
+		state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+		result.push((state >> 8) as f32 / (1 << 24) as f32 - 0.5);
+	}
+
+	return result;
+}
+
+// Best of repeated runs, repeated until they cover at least half a second
+
+pub fn time<F: FnMut()>(mut f: F) -> Duration {
+	let mut best = Duration::MAX;
+	let mut total = Duration::ZERO;
+
+	while total < Duration::from_millis(500) {
+		let start = Instant::now();
+		f();
+		let elapsed = start.elapsed();
+
+		total += elapsed;
+		best = best.min(elapsed);
+	}
+
+	return best;
+}
@@ -0,0 +1,71 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   mul_mat.rs                                         :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:58:35 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:39:36 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// The blocked kernel picks SIMD FMA at runtime, while the naive kernel's
+// f32::mul_add lowers to a libm call unless FMA is enabled at compile time.
+// Run with RUSTFLAGS="-C target-cpu=native" to compare the memory access
+// patterns alone.
+
+mod common;
+
+use std::hint::black_box;
+
+use common::{fill, time};
+use matrix::gemm::{gemm_blocked, gemm_naive};
+use matrix::matrix::Matrix;
+
+fn bench_size(n: usize) {
+	let a = fill(n * n, 1);
+	let b = fill(n * n, 2);
+	let mut c_naive = vec![0.; n * n];
+	let mut c_blocked = vec![0.; n * n];
+
+	let naive = time(|| gemm_naive(black_box(&a), black_box(&b), &mut c_naive, n, n, n));
+	let blocked = time(|| gemm_blocked(black_box(&a), black_box(&b), &mut c_blocked, n, n, n));
+
+	if c_naive != c_blocked {
+		panic!("Error: Blocked result differs from the naive result");
+	}
+
+	println!(
+		"{:>4}x{:<4} naive {:>10.3?}  blocked {:>10.3?}  speedup {:>5.2}x",
+		n,
+		n,
+		naive,
+		blocked,
+		naive.as_secs_f64() / blocked.as_secs_f64()
+	);
+}
+
+fn bench_matrix() {
+	let mut data = [[0f32; 64]; 64];
+	let values = fill(64 * 64, 3);
+
+	for i in 0..64 {
+		data[i].copy_from_slice(&values[i * 64..(i + 1) * 64]);
+	}
+
+	let u = Matrix::new(data);
+	let v = u.transpose();
+	let elapsed = time(|| {
+		black_box(u.mul_mat(v.clone()));
+	});
+
+	println!("  64x64   Matrix::mul_mat {:>10.3?}", elapsed);
+}
+
+fn main() {
+	for n in [64, 128, 256, 512] {
+		bench_size(n);
+	}
+	bench_matrix();
+}
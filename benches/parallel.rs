@@ -0,0 +1,83 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   parallel.rs                                        :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:00:02 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:39:36 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+mod common;
+
+use std::hint::black_box;
+
+use common::{fill, time};
+use matrix::gemm::gemm_blocked;
+use matrix::matrix::Matrix;
+use matrix::parallel;
+
+fn bench_gemm(n: usize) {
+	let a = fill(n * n, 1);
+	let b = fill(n * n, 2);
+	let mut c_serial = vec![0.; n * n];
+	let mut c_parallel = vec![0.; n * n];
+
+	parallel::set_threshold(0);
+
+	let serial = time(|| gemm_blocked(black_box(&a), black_box(&b), &mut c_serial, n, n, n));
+	let threaded = time(|| parallel::gemm(black_box(&a), black_box(&b), &mut c_parallel, n, n, n));
+
+	if c_serial != c_parallel {
+		panic!("Error: Parallel result differs from the serial result");
+	}
+
+	println!(
+		"gemm         {:>4}x{:<4} serial {:>10.3?}  parallel {:>10.3?}  speedup {:>5.2}x",
+		n,
+		n,
+		serial,
+		threaded,
+		serial.as_secs_f64() / threaded.as_secs_f64()
+	);
+}
+
+fn bench_row_echelon<const N: usize>() {
+	let values = fill(N * N, 3);
+	let mut data = Box::new([[0f32; N]; N]);
+
+	for i in 0..N {
+		data[i].copy_from_slice(&values[i * N..(i + 1) * N]);
+	}
+
+	let u = Box::new(Matrix::new(*data));
+
+	parallel::set_threshold(usize::MAX);
+	let serial = time(|| {
+		black_box(u.row_echelon());
+	});
+
+	parallel::set_threshold(0);
+	let threaded = time(|| {
+		black_box(u.row_echelon());
+	});
+
+	println!(
+		"row_echelon  {:>4}x{:<4} serial {:>10.3?}  parallel {:>10.3?}  speedup {:>5.2}x",
+		N,
+		N,
+		serial,
+		threaded,
+		serial.as_secs_f64() / threaded.as_secs_f64()
+	);
+}
+
+fn main() {
+	for n in [128, 256, 512] {
+		bench_gemm(n);
+	}
+	bench_row_echelon::<128>();
+	bench_row_echelon::<256>();
+}
@@ -0,0 +1,372 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   binary.rs                                          :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:21:07 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:12:47 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+
+use crate::checksum::crc32;
+use crate::matrix::Matrix;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+// Record layout, little-endian:
+//   0  magic "MTRX"
+//   4  version, scalar tag, kind (0 vector, 1 matrix), reserved
+//   8  rows u32, cols u32 (a vector is a single column)
+//   16 payload, row-major
+//   .. CRC-32 of everything before it
+// The 16-byte header keeps the payload aligned for f32 and f64.
+
+pub const MAGIC: [u8; 4] = *b"MTRX";
+pub const VERSION: u8 = 1;
+
+const HEADER: usize = 16;
+const VECTOR: u8 = 0;
+const MATRIX: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+	Magic,
+	Version(u8),
+	Scalar {
+		expected: u8,
+		found: u8,
+	},
+	Kind {
+		expected: u8,
+		found: u8,
+	},
+	UnknownKind(u8),
+	Reserved(u8),
+	Dimensions {
+		expected: (usize, usize),
+		found: (usize, usize),
+	},
+	Truncated {
+		expected: usize,
+		found: usize,
+	},
+	SizeOverflow {
+		rows: usize,
+		cols: usize,
+	},
+	Checksum {
+		expected: u32,
+		found: u32,
+	},
+}
+
+impl fmt::Display for BinaryError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			BinaryError::Magic => write!(f, "not a matrix record"),
+			BinaryError::Version(version) => write!(f, "unsupported version {}", version),
+			BinaryError::Scalar { expected, found } => {
+				write!(f, "scalar tag {}, expected {}", found, expected)
+			}
+			BinaryError::Kind { expected, found } => {
+				write!(f, "record kind {}, expected {}", found, expected)
+			}
+			BinaryError::UnknownKind(kind) => write!(f, "unknown record kind {}", kind),
+			BinaryError::Reserved(byte) => write!(f, "reserved byte {}, expected 0", byte),
+			BinaryError::Dimensions { expected, found } => write!(
+				f,
+				"expected {}x{} values, found {}x{}",
+				expected.0, expected.1, found.0, found.1
+			),
+			BinaryError::Truncated { expected, found } => {
+				write!(f, "{} bytes, expected {}", found, expected)
+			}
+			BinaryError::SizeOverflow { rows, cols } => {
+				write!(f, "{}x{} values do not fit in memory", rows, cols)
+			}
+			BinaryError::Checksum { expected, found } => {
+				write!(f, "checksum {:08x}, expected {:08x}", found, expected)
+			}
+		}
+	}
+}
+
+impl Error for BinaryError {}
+
+mod sealed {
+	pub trait Sealed {}
+
+	impl Sealed for f32 {}
+	impl Sealed for f64 {}
+}
+
+// Scalars with a fixed-size little-endian encoding. Sealed, since views
+// reinterpret payload bytes as these types.
+
+pub trait Encode: sealed::Sealed + Copy + 'static {
+	const TAG: u8;
+	const SIZE: usize;
+
+	fn encode(self, out: &mut Vec<u8>);
+	fn decode(bytes: &[u8]) -> Self;
+}
+
+impl Encode for f32 {
+	const TAG: u8 = 1;
+	const SIZE: usize = 4;
+
+	fn encode(self, out: &mut Vec<u8>) {
+		out.extend_from_slice(&self.to_le_bytes());
+	}
+
+	fn decode(bytes: &[u8]) -> Self {
+		return f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+	}
+}
+
+impl Encode for f64 {
+	const TAG: u8 = 2;
+	const SIZE: usize = 8;
+
+	fn encode(self, out: &mut Vec<u8>) {
+		out.extend_from_slice(&self.to_le_bytes());
+	}
+
+	fn decode(bytes: &[u8]) -> Self {
+		let mut array = [0u8; 8];
+
+		array.copy_from_slice(&bytes[..8]);
+
+		return f64::from_le_bytes(array);
+	}
+}
+
+fn encode<K: Encode>(kind: u8, rows: usize, cols: usize, values: &[K]) -> Vec<u8> {
+	let (Ok(rows), Ok(cols)) = (u32::try_from(rows), u32::try_from(cols)) else {
+		panic!("Error: Dimensions do not fit in the 32-bit header");
+	};
+	let mut out = Vec::with_capacity(HEADER + values.len() * K::SIZE + 4);
+
+	out.extend_from_slice(&MAGIC);
+	out.extend_from_slice(&[VERSION, K::TAG, kind, 0]);
+	out.extend_from_slice(&rows.to_le_bytes());
+	out.extend_from_slice(&cols.to_le_bytes());
+	for &value in values {
+		value.encode(&mut out);
+	}
+
+	let checksum = crc32(&out);
+
+	out.extend_from_slice(&checksum.to_le_bytes());
+
+	return out;
+}
+
+pub fn encode_matrix<K: Encode>(rows: usize, cols: usize, values: &[K]) -> Vec<u8> {
+	if values.len() != rows * cols {
+		panic!("Error: Arrays sizes are different");
+	}
+
+	return encode(MATRIX, rows, cols, values);
+}
+
+pub fn encode_vector<K: Encode>(values: &[K]) -> Vec<u8> {
+	return encode(VECTOR, values.len(), 1, values);
+}
+
+// A validated record borrowed from a byte slice, values decoded on access
+
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, K> {
+	pub rows: usize,
+	pub cols: usize,
+	kind: u8,
+	payload: &'a [u8],
+	marker: PhantomData<K>,
+}
+
+impl<'a, K: Encode> View<'a, K> {
+	pub fn parse(bytes: &'a [u8]) -> Result<Self, BinaryError> {
+		if bytes.len() < HEADER {
+			return Err(BinaryError::Truncated {
+				expected: HEADER,
+				found: bytes.len(),
+			});
+		}
+		if bytes[..4] != MAGIC {
+			return Err(BinaryError::Magic);
+		}
+		if bytes[4] != VERSION {
+			return Err(BinaryError::Version(bytes[4]));
+		}
+		if bytes[5] != K::TAG {
+			return Err(BinaryError::Scalar {
+				expected: K::TAG,
+				found: bytes[5],
+			});
+		}
+		if bytes[6] != VECTOR && bytes[6] != MATRIX {
+			return Err(BinaryError::UnknownKind(bytes[6]));
+		}
+		if bytes[7] != 0 {
+			return Err(BinaryError::Reserved(bytes[7]));
+		}
+
+		let word = |at: usize| {
+			u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+		};
+		let (rows, cols) = (word(8) as usize, word(12) as usize);
+
+		if bytes[6] == VECTOR && cols != 1 {
+			return Err(BinaryError::Dimensions {
+				expected: (rows, 1),
+				found: (rows, cols),
+			});
+		}
+
+		// The dimensions are untrusted, a size past usize::MAX can't fit either
+
+		let total = rows
+			.checked_mul(cols)
+			.and_then(|count| count.checked_mul(K::SIZE))
+			.and_then(|size| size.checked_add(HEADER + 4));
+
+		let Some(total) = total else {
+			return Err(BinaryError::SizeOverflow { rows, cols });
+		};
+
+		if total > bytes.len() {
+			return Err(BinaryError::Truncated {
+				expected: total,
+				found: bytes.len(),
+			});
+		}
+
+		let end = total - 4;
+		let expected = word(end);
+		let found = crc32(&bytes[..end]);
+
+		if found != expected {
+			return Err(BinaryError::Checksum { expected, found });
+		}
+
+		return Ok(View {
+			rows,
+			cols,
+			kind: bytes[6],
+			payload: &bytes[HEADER..end],
+			marker: PhantomData,
+		});
+	}
+
+	// Size of the whole record, to step through concatenated ones
+
+	pub fn encoded_len(&self) -> usize {
+		return HEADER + self.payload.len() + 4;
+	}
+
+	pub fn is_matrix(&self) -> bool {
+		return self.kind == MATRIX;
+	}
+
+	pub fn get(&self, i: usize, j: usize) -> K {
+		if i >= self.rows || j >= self.cols {
+			panic!("Error: Index out of bounds");
+		}
+
+		let at = (i * self.cols + j) * K::SIZE;
+
+		return K::decode(&self.payload[at..at + K::SIZE]);
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = K> + 'a {
+		return self.payload.chunks_exact(K::SIZE).map(K::decode);
+	}
+
+	// The payload itself, when it happens to be aligned on a little-endian
+	// target
+
+	pub fn as_slice(&self) -> Option<&'a [K]> {
+		if cfg!(target_endian = "big") {
+			return None;
+		}
+
+		// Safe as Encode is only implemented for f32 and f64, for which
+		// every bit pattern is a valid value
+		let (prefix, values, suffix) = unsafe { self.payload.align_to::<K>() };
+
+		if !prefix.is_empty() || !suffix.is_empty() {
+			return None;
+		}
+
+		return Some(values);
+	}
+
+	fn check(&self, kind: u8, expected: (usize, usize)) -> Result<(), BinaryError> {
+		if self.kind != kind {
+			return Err(BinaryError::Kind {
+				expected: kind,
+				found: self.kind,
+			});
+		}
+		if (self.rows, self.cols) != expected {
+			return Err(BinaryError::Dimensions {
+				expected,
+				found: (self.rows, self.cols),
+			});
+		}
+
+		return Ok(());
+	}
+}
+
+impl<'a, K: Traits + Encode> View<'a, K> {
+	pub fn to_matrix<const M: usize, const N: usize>(
+		&self,
+	) -> Result<Matrix<K, M, N>, BinaryError> {
+		self.check(MATRIX, (M, N))?;
+
+		return Ok(Matrix::from_fn(|i, j| self.get(i, j)));
+	}
+
+	pub fn to_vector<const N: usize>(&self) -> Result<Vector<K, N>, BinaryError> {
+		self.check(VECTOR, (N, 1))?;
+
+		return Ok(Vector::new(std::array::from_fn(|i| self.get(i, 0))));
+	}
+}
+
+impl<K: Traits + Encode, const M: usize, const N: usize> Matrix<K, M, N> {
+	pub fn to_bytes(&self) -> Vec<u8> {
+		return encode_matrix(M, N, self.data.as_flattened());
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+		return View::parse(bytes)?.to_matrix();
+	}
+
+	pub fn write_binary<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		return writer.write_all(&self.to_bytes());
+	}
+}
+
+impl<K: Traits + Encode, const N: usize> Vector<K, N> {
+	pub fn to_bytes(&self) -> Vec<u8> {
+		return encode_vector(&self.data);
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+		return View::parse(bytes)?.to_vector();
+	}
+
+	pub fn write_binary<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		return writer.write_all(&self.to_bytes());
+	}
+}
@@ -0,0 +1,349 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   blas.rs                                            :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:02:36 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:41:24 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// BLAS-style routines on top of Vector and Matrix. Arguments follow the
+// reference BLAS order, with op(X) = X or X^T selected by a Transpose flag.
+// Since a transposed operand changes which dimensions have to agree, those
+// are checked at runtime. As in BLAS, C and y are not read when beta is 0.
+
+use crate::matrix::Matrix;
+use crate::parallel;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transpose {
+	No,
+	Yes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Uplo {
+	Upper,
+	Lower,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Diag {
+	NonUnit,
+	Unit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+	Left,
+	Right,
+}
+
+impl Transpose {
+	fn flip(self) -> Transpose {
+		match self {
+			Transpose::No => Transpose::Yes,
+			Transpose::Yes => Transpose::No,
+		}
+	}
+}
+
+fn op_size<const M: usize, const N: usize>(trans: Transpose) -> (usize, usize) {
+	match trans {
+		Transpose::No => (M, N),
+		Transpose::Yes => (N, M),
+	}
+}
+
+fn op_at<K: Traits, const M: usize, const N: usize>(
+	a: &Matrix<K, M, N>,
+	trans: Transpose,
+	i: usize,
+	j: usize,
+) -> K {
+	match trans {
+		Transpose::No => a.data[i][j],
+		Transpose::Yes => a.data[j][i],
+	}
+}
+
+fn op_flat<K: Traits, const M: usize, const N: usize>(
+	a: &Matrix<K, M, N>,
+	trans: Transpose,
+) -> Vec<K> {
+	match trans {
+		Transpose::No => a.data.as_flattened().to_vec(),
+		Transpose::Yes => a.transpose().data.as_flattened().to_vec(),
+	}
+}
+
+fn scale_output<K: Traits>(beta: K, y: &mut K, value: K) {
+	if beta == K::default() {
+		*y = value;
+	} else {
+		*y = beta.mul_add(*y, value);
+	}
+}
+
+// Solves op(A) x = b in place, where op(A) is triangular
+
+fn solve_triangular<K: Traits, const N: usize>(
+	a: &Matrix<K, N, N>,
+	uplo: Uplo,
+	trans: Transpose,
+	diag: Diag,
+	x: &mut [K],
+) {
+	let lower = (uplo == Uplo::Lower) == (trans == Transpose::No);
+
+	if lower {
+		for i in 0..N {
+			let mut sum = x[i];
+
+			for j in 0..i {
+				sum = (-op_at(a, trans, i, j)).mul_add(x[j], sum);
+			}
+			if diag == Diag::NonUnit {
+				sum /= op_at(a, trans, i, i);
+			}
+			x[i] = sum;
+		}
+	} else {
+		for i in (0..N).rev() {
+			let mut sum = x[i];
+
+			for j in i + 1..N {
+				sum = (-op_at(a, trans, i, j)).mul_add(x[j], sum);
+			}
+			if diag == Diag::NonUnit {
+				sum /= op_at(a, trans, i, i);
+			}
+			x[i] = sum;
+		}
+	}
+}
+
+// Level 1
+
+pub fn axpy<K: Traits, const N: usize>(alpha: K, x: &Vector<K, N>, y: &mut Vector<K, N>) {
+	K::axpy(alpha, &x.data, &mut y.data);
+}
+
+pub fn scal<K: Traits, const N: usize>(alpha: K, x: &mut Vector<K, N>) {
+	x.scl(alpha);
+}
+
+pub fn dot<K: Traits, const N: usize>(x: &Vector<K, N>, y: &Vector<K, N>) -> K {
+	return x.dot(y.clone());
+}
+
+pub fn nrm2<K: Traits, const N: usize>(x: &Vector<K, N>) -> f32 {
+	return x.norm_2();
+}
+
+pub fn asum<K: Traits, const N: usize>(x: &Vector<K, N>) -> f32 {
+	return x.norm_1();
+}
+
+pub fn iamax<K: Traits, const N: usize>(x: &Vector<K, N>) -> usize {
+	let mut result = 0;
+
+	for i in 1..N {
+		if x.data[i].into().abs() > x.data[result].into().abs() {
+			result = i;
+		}
+	}
+
+	return result;
+}
+
+// Level 2
+
+pub fn gemv<K: Traits, const M: usize, const N: usize, const P: usize, const Q: usize>(
+	trans: Transpose,
+	alpha: K,
+	a: &Matrix<K, M, N>,
+	x: &Vector<K, P>,
+	beta: K,
+	y: &mut Vector<K, Q>,
+) {
+	let (rows, cols) = op_size::<M, N>(trans);
+
+	if cols != P || rows != Q {
+		panic!("Error: Matrix and vector sizes are incompatible");
+	}
+
+	for i in 0..rows {
+		let mut sum = K::default();
+
+		for k in 0..cols {
+			sum = op_at(a, trans, i, k).mul_add(x.data[k], sum);
+		}
+		scale_output(beta, &mut y.data[i], alpha * sum);
+	}
+}
+
+pub fn ger<K: Traits, const M: usize, const N: usize>(
+	alpha: K,
+	x: &Vector<K, M>,
+	y: &Vector<K, N>,
+	a: &mut Matrix<K, M, N>,
+) {
+	for i in 0..M {
+		K::axpy(alpha * x.data[i], &y.data, &mut a.data[i]);
+	}
+}
+
+pub fn trsv<K: Traits, const N: usize>(
+	uplo: Uplo,
+	trans: Transpose,
+	diag: Diag,
+	a: &Matrix<K, N, N>,
+	x: &mut Vector<K, N>,
+) {
+	solve_triangular(a, uplo, trans, diag, &mut x.data);
+}
+
+// Level 3
+
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<
+	K: Traits,
+	const AM: usize,
+	const AN: usize,
+	const BM: usize,
+	const BN: usize,
+	const CM: usize,
+	const CN: usize,
+>(
+	transa: Transpose,
+	transb: Transpose,
+	alpha: K,
+	a: &Matrix<K, AM, AN>,
+	b: &Matrix<K, BM, BN>,
+	beta: K,
+	c: &mut Matrix<K, CM, CN>,
+) {
+	let (m, k) = op_size::<AM, AN>(transa);
+	let (kb, n) = op_size::<BM, BN>(transb);
+
+	if k != kb || m != CM || n != CN {
+		panic!("Error: Matrix sizes are incompatible");
+	}
+
+	// alpha = 1, beta = 0 without transposes is exactly mul_mat
+
+	if transa == Transpose::No
+		&& transb == Transpose::No
+		&& alpha == K::from(1.)
+		&& beta == K::default()
+	{
+		parallel::gemm(
+			a.data.as_flattened(),
+			b.data.as_flattened(),
+			c.data.as_flattened_mut(),
+			m,
+			k,
+			n,
+		);
+		return;
+	}
+
+	let mut product = vec![K::default(); m * n];
+	parallel::gemm(
+		&op_flat(a, transa),
+		&op_flat(b, transb),
+		&mut product,
+		m,
+		k,
+		n,
+	);
+
+	for i in 0..m {
+		for j in 0..n {
+			scale_output(beta, &mut c.data[i][j], alpha * product[i * n + j]);
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn trsm<K: Traits, const N: usize, const M: usize, const P: usize>(
+	side: Side,
+	uplo: Uplo,
+	trans: Transpose,
+	diag: Diag,
+	alpha: K,
+	a: &Matrix<K, N, N>,
+	b: &mut Matrix<K, M, P>,
+) {
+	match side {
+		// op(A) X = alpha B, one column of B at a time
+		Side::Left => {
+			if M != N {
+				panic!("Error: Matrix sizes are incompatible");
+			}
+
+			let mut column = vec![K::default(); N];
+
+			for j in 0..P {
+				for i in 0..N {
+					column[i] = alpha * b.data[i][j];
+				}
+				solve_triangular(a, uplo, trans, diag, &mut column);
+				for i in 0..N {
+					b.data[i][j] = column[i];
+				}
+			}
+		}
+
+		// X op(A) = alpha B, i.e. op(A)^T x^T = alpha b^T for each row
+		Side::Right => {
+			if P != N {
+				panic!("Error: Matrix sizes are incompatible");
+			}
+
+			for i in 0..M {
+				b.data[i].iter_mut().for_each(|value| *value *= alpha);
+				solve_triangular(a, uplo, trans.flip(), diag, &mut b.data[i]);
+			}
+		}
+	}
+}
+
+pub fn syrk<K: Traits, const N: usize, const AM: usize, const AN: usize>(
+	uplo: Uplo,
+	trans: Transpose,
+	alpha: K,
+	a: &Matrix<K, AM, AN>,
+	beta: K,
+	c: &mut Matrix<K, N, N>,
+) {
+	// C = alpha op(A) op(A)^T + beta C, with op(A) of size N x depth
+
+	let (rows, depth) = op_size::<AM, AN>(trans);
+
+	if rows != N {
+		panic!("Error: Matrix sizes are incompatible");
+	}
+
+	for i in 0..N {
+		let (start, end) = match uplo {
+			Uplo::Upper => (i, N),
+			Uplo::Lower => (0, i + 1),
+		};
+
+		for j in start..end {
+			let mut sum = K::default();
+
+			for k in 0..depth {
+				sum = op_at(a, trans, i, k).mul_add(op_at(a, trans, j, k), sum);
+			}
+			scale_output(beta, &mut c.data[i][j], alpha * sum);
+		}
+	}
+}
@@ -0,0 +1,773 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   calculator.rs                                      :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:23:01 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:10:24 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::elimination::{eliminate, solve_augmented, tolerance, EliminationRecord, Form};
+use crate::format::{write_rows, FormatOptions};
+use crate::lerp;
+use crate::parallel;
+use crate::parse::parse_rows;
+use crate::vector::Vector;
+
+// Sizes are only known at run time here, so values keep their shape next
+// to the data and go through the slice-based kernels of the library
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	Scalar(f32),
+	Vector(Vec<f32>),
+	Matrix {
+		rows: usize,
+		cols: usize,
+		data: Vec<f32>,
+	},
+}
+
+// Singular covers inputs that are well formed but have no inverse or no
+// unique solution
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+	Invalid,
+	Singular,
+}
+
+// Columns count from 1, in characters
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcError {
+	pub kind: ErrorKind,
+	pub message: String,
+	pub column: Option<usize>,
+}
+
+impl fmt::Display for CalcError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.column {
+			Some(column) => write!(f, "column {}: {}", column, self.message),
+			None => write!(f, "{}", self.message),
+		}
+	}
+}
+
+impl Error for CalcError {}
+
+fn singular<T>(message: impl Into<String>) -> Result<T, CalcError> {
+	return Err(CalcError {
+		kind: ErrorKind::Singular,
+		message: message.into(),
+		column: None,
+	});
+}
+
+fn error<T>(message: impl Into<String>) -> Result<T, CalcError> {
+	return Err(CalcError {
+		kind: ErrorKind::Invalid,
+		message: message.into(),
+		column: None,
+	});
+}
+
+impl Value {
+	pub fn kind(&self) -> String {
+		match self {
+			Value::Scalar(_) => String::from("scalar"),
+			Value::Vector(v) => format!("vector of {}", v.len()),
+			Value::Matrix { rows, cols, .. } => format!("{}x{} matrix", rows, cols),
+		}
+	}
+
+	fn square(&self, name: &str) -> Result<(usize, &[f32]), CalcError> {
+		match self {
+			Value::Matrix { rows, cols, .. } if rows == cols => {
+				let (n, _, data) = matrix_arg(name, self)?;
+
+				Ok((n, data))
+			}
+			other => error(format!(
+				"{} expects a square matrix, got a {}",
+				name,
+				other.kind()
+			)),
+		}
+	}
+
+	fn map(&self, f: impl Fn(f32) -> f32) -> Value {
+		match self {
+			Value::Scalar(x) => Value::Scalar(f(*x)),
+			Value::Vector(v) => Value::Vector(v.iter().map(|&x| f(x)).collect()),
+			Value::Matrix { rows, cols, data } => Value::Matrix {
+				rows: *rows,
+				cols: *cols,
+				data: data.iter().map(|&x| f(x)).collect(),
+			},
+		}
+	}
+
+	fn zip(
+		&self,
+		other: &Value,
+		op: &str,
+		f: impl Fn(f32, f32) -> f32,
+	) -> Result<Value, CalcError> {
+		let zip = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(&x, &y)| f(x, y)).collect();
+
+		match (self, other) {
+			(Value::Scalar(a), Value::Scalar(b)) => Ok(Value::Scalar(f(*a, *b))),
+			(Value::Vector(a), Value::Vector(b)) if a.len() == b.len() => {
+				Ok(Value::Vector(zip(a, b)))
+			}
+			(
+				Value::Matrix {
+					rows,
+					cols,
+					data: a,
+				},
+				Value::Matrix {
+					rows: r,
+					cols: c,
+					data: b,
+				},
+			) if (rows, cols) == (r, c) => Ok(Value::Matrix {
+				rows: *rows,
+				cols: *cols,
+				data: zip(a, b),
+			}),
+			(a, b) => error(format!("cannot {} a {} and a {}", op, a.kind(), b.kind())),
+		}
+	}
+
+	fn mul(&self, other: &Value) -> Result<Value, CalcError> {
+		match (self, other) {
+			(Value::Scalar(a), b) | (b, Value::Scalar(a)) => Ok(b.map(|x| a * x)),
+			(
+				Value::Matrix {
+					rows,
+					cols,
+					data: a,
+				},
+				Value::Matrix {
+					rows: n,
+					cols: p,
+					data: b,
+				},
+			) if cols == n => {
+				let mut c = vec![0.; rows * p];
+
+				parallel::gemm(a, b, &mut c, *rows, *cols, *p);
+
+				Ok(Value::Matrix {
+					rows: *rows,
+					cols: *p,
+					data: c,
+				})
+			}
+			(Value::Matrix { rows, cols, data }, Value::Vector(v)) if *cols == v.len() => {
+				Ok(Value::Vector(
+					(0..*rows)
+						.map(|i| dot(&data[i * cols..(i + 1) * cols], v))
+						.collect(),
+				))
+			}
+			(Value::Vector(_), Value::Vector(_)) => {
+				error("cannot multiply two vectors, use dot(u, v) or cross(u, v)")
+			}
+			(a, b) => error(format!("cannot multiply a {} by a {}", a.kind(), b.kind())),
+		}
+	}
+}
+
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Value::Scalar(x) => writeln!(f, "{}", x),
+			Value::Vector(v) => {
+				let rows: Vec<[f32; 1]> = v.iter().map(|&x| [x]).collect();
+
+				write_rows(f, &rows, &FormatOptions::default())
+			}
+			Value::Matrix { cols, data, .. } => {
+				let rows: Vec<&[f32]> = data.chunks((*cols).max(1)).collect();
+
+				write_rows(f, &rows, &FormatOptions::default())
+			}
+		}
+	}
+}
+
+// The same mul_add chain as Vector::dot and Matrix::mul_vec, so results
+// match the library to the last bit
+
+fn dot(u: &[f32], v: &[f32]) -> f32 {
+	return u.iter().zip(v).fold(0., |acc, (&x, &y)| x.mul_add(y, acc));
+}
+
+fn determinant(n: usize, data: &[f32]) -> f32 {
+	let mut rows: Vec<Vec<f32>> = data.chunks(n).map(|row| row.to_vec()).collect();
+	let tolerance = tolerance(&rows);
+	let mut record = EliminationRecord::new();
+
+	eliminate(&mut rows, n, Form::Echelon, tolerance, Some(&mut record));
+
+	if record.rank() < n {
+		return 0.;
+	}
+
+	let sign = if record.swaps().len() % 2 == 0 {
+		1.
+	} else {
+		-1.
+	};
+
+	return (0..n).fold(sign, |acc, i| acc * rows[i][i]);
+}
+
+fn inverse(n: usize, data: &[f32]) -> Result<Value, CalcError> {
+	let tolerance = tolerance(&data.chunks(n).collect::<Vec<_>>());
+	let mut augmented: Vec<Vec<f32>> = (0..n)
+		.map(|i| {
+			let mut row = data[i * n..(i + 1) * n].to_vec();
+			row.extend((0..n).map(|j| if i == j { 1. } else { 0. }));
+			row
+		})
+		.collect();
+	let mut record = EliminationRecord::new();
+
+	eliminate(
+		&mut augmented,
+		n,
+		Form::Reduced,
+		tolerance,
+		Some(&mut record),
+	);
+
+	if record.rank() < n {
+		return singular("matrix is singular, it has no inverse");
+	}
+
+	return Ok(Value::Matrix {
+		rows: n,
+		cols: n,
+		data: augmented.iter().flat_map(|row| row[n..].to_vec()).collect(),
+	});
+}
+
+// Values set from outside may be empty, functions need at least one entry
+
+fn matrix_arg<'a>(name: &str, value: &'a Value) -> Result<(usize, usize, &'a [f32]), CalcError> {
+	match value {
+		Value::Matrix { rows, cols, .. } if *rows == 0 || *cols == 0 => error(format!(
+			"{} expects a non-empty matrix, got a {}",
+			name,
+			value.kind()
+		)),
+		Value::Matrix { rows, cols, data } => Ok((*rows, *cols, data)),
+		other => error(format!("{} expects a matrix, got a {}", name, other.kind())),
+	}
+}
+
+fn vector_arg<'a>(name: &str, value: &'a Value) -> Result<&'a [f32], CalcError> {
+	match value {
+		Value::Vector(v) if v.is_empty() => error(format!(
+			"{} expects a non-empty vector, got a {}",
+			name,
+			value.kind()
+		)),
+		Value::Vector(v) => Ok(v),
+		other => error(format!("{} expects a vector, got a {}", name, other.kind())),
+	}
+}
+
+const FUNCTIONS: [(&str, usize); 10] = [
+	("det", 1),
+	("inv", 1),
+	("rank", 1),
+	("rref", 1),
+	("trace", 1),
+	("transpose", 1),
+	("dot", 2),
+	("cross", 2),
+	("lerp", 3),
+	("solve", 2),
+];
+
+fn call(name: &str, args: &[Value]) -> Result<Value, CalcError> {
+	let arity = match FUNCTIONS.iter().find(|(function, _)| *function == name) {
+		Some(&(_, arity)) => arity,
+		None => return error(format!("unknown function '{}'", name)),
+	};
+
+	if args.len() != arity {
+		return error(format!(
+			"{} takes {} argument(s), got {}",
+			name,
+			arity,
+			args.len()
+		));
+	}
+
+	match name {
+		"det" => {
+			let (n, data) = args[0].square(name)?;
+
+			Ok(Value::Scalar(determinant(n, data)))
+		}
+		"inv" => {
+			let (n, data) = args[0].square(name)?;
+
+			inverse(n, data)
+		}
+		"rank" | "rref" => {
+			let (rows, cols, data) = matrix_arg(name, &args[0])?;
+			let mut chunks: Vec<Vec<f32>> = data.chunks(cols).map(|row| row.to_vec()).collect();
+			let mut record = EliminationRecord::new();
+			let form = if name == "rank" {
+				Form::Echelon
+			} else {
+				Form::Reduced
+			};
+			let tolerance = tolerance(&chunks);
+
+			eliminate(&mut chunks, cols, form, tolerance, Some(&mut record));
+
+			match name {
+				"rank" => Ok(Value::Scalar(record.rank() as f32)),
+				_ => Ok(Value::Matrix {
+					rows,
+					cols,
+					data: chunks.concat(),
+				}),
+			}
+		}
+		"trace" => {
+			let (n, data) = args[0].square(name)?;
+
+			Ok(Value::Scalar((0..n).map(|i| data[i * n + i]).sum()))
+		}
+		"transpose" => match &args[0] {
+			Value::Scalar(x) => Ok(Value::Scalar(*x)),
+			Value::Vector(v) => Ok(Value::Matrix {
+				rows: 1,
+				cols: v.len(),
+				data: v.clone(),
+			}),
+			Value::Matrix { rows, cols, data } => Ok(Value::Matrix {
+				rows: *cols,
+				cols: *rows,
+				data: (0..rows * cols)
+					.map(|k| data[(k % rows) * cols + k / rows])
+					.collect(),
+			}),
+		},
+		"dot" => {
+			let (u, v) = (vector_arg(name, &args[0])?, vector_arg(name, &args[1])?);
+
+			if u.len() != v.len() {
+				return error(format!(
+					"dot needs vectors of the same size, got {} and {}",
+					u.len(),
+					v.len()
+				));
+			}
+
+			Ok(Value::Scalar(dot(u, v)))
+		}
+		"cross" => {
+			let (u, v) = (vector_arg(name, &args[0])?, vector_arg(name, &args[1])?);
+
+			if u.len() != 3 || v.len() != 3 {
+				return error("cross needs two vectors of 3");
+			}
+
+			let result = Vector::<f32, 3>::cross_product(
+				&Vector::new([u[0], u[1], u[2]]),
+				&Vector::new([v[0], v[1], v[2]]),
+			);
+
+			Ok(Value::Vector(result.data.to_vec()))
+		}
+		"solve" => {
+			let (rows, cols, data) = matrix_arg(name, &args[0])?;
+			let b = vector_arg(name, &args[1])?;
+
+			if b.len() != rows {
+				return error(format!("solve needs a vector of {}, got {}", rows, b.len()));
+			}
+
+			let augmented: Vec<Vec<f32>> = data
+				.chunks(cols)
+				.zip(b)
+				.map(|(row, &value)| [row, &[value]].concat())
+				.collect();
+
+			match solve_augmented(augmented, None) {
+				None => singular("system is inconsistent, it has no solution"),
+				Some((_, null_space)) if !null_space.is_empty() => {
+					singular("system has infinitely many solutions")
+				}
+				Some((solution, _)) => Ok(Value::Vector(solution)),
+			}
+		}
+		_ => {
+			let t = match args[2] {
+				Value::Scalar(t) => t,
+				ref other => {
+					return error(format!("lerp expects a scalar t, got a {}", other.kind()))
+				}
+			};
+
+			args[0].zip(&args[1], "interpolate", |a, b| lerp(a, b, t))
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Number(f32),
+	Name(String),
+	Literal(String),
+	Operator(char),
+	End,
+}
+
+fn tokenize(line: &str) -> Result<Vec<(Token, usize)>, CalcError> {
+	let chars: Vec<char> = line.chars().collect();
+	let mut tokens = Vec::new();
+	let mut i = 0;
+
+	while i < chars.len() {
+		let c = chars[i];
+		let column = i + 1;
+
+		if c.is_whitespace() {
+			i += 1;
+		} else if c.is_ascii_digit() || c == '.' {
+			let start = i;
+
+			while i < chars.len()
+				&& (chars[i].is_ascii_alphanumeric()
+					|| chars[i] == '.'
+					|| (matches!(chars[i], '-' | '+') && matches!(chars[i - 1], 'e' | 'E')))
+			{
+				i += 1;
+			}
+
+			let text: String = chars[start..i].iter().collect();
+			let value = text.parse::<f32>().map_err(|_| CalcError {
+				kind: ErrorKind::Invalid,
+				message: format!("invalid number '{}'", text),
+				column: Some(column),
+			})?;
+
+			tokens.push((Token::Number(value), column));
+		} else if c.is_alphabetic() || c == '_' {
+			let start = i;
+
+			while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+				i += 1;
+			}
+			tokens.push((Token::Name(chars[start..i].iter().collect()), column));
+		} else if c == '[' {
+			let start = i;
+			let mut depth = 0;
+
+			while i < chars.len() {
+				match chars[i] {
+					'[' => depth += 1,
+					']' => depth -= 1,
+					_ => {}
+				}
+				i += 1;
+				if depth == 0 {
+					break;
+				}
+			}
+			if depth != 0 {
+				return Err(CalcError {
+					kind: ErrorKind::Invalid,
+					message: String::from("unclosed '['"),
+					column: Some(column),
+				});
+			}
+			tokens.push((Token::Literal(chars[start..i].iter().collect()), column));
+		} else if "+-*/(),=".contains(c) {
+			tokens.push((Token::Operator(c), column));
+			i += 1;
+		} else {
+			return Err(CalcError {
+				kind: ErrorKind::Invalid,
+				message: format!("unexpected character '{}'", c),
+				column: Some(column),
+			});
+		}
+	}
+
+	tokens.push((Token::End, chars.len() + 1));
+
+	return Ok(tokens);
+}
+
+struct Parser<'a> {
+	tokens: Vec<(Token, usize)>,
+	position: usize,
+	variables: &'a HashMap<String, Value>,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> &Token {
+		return &self.tokens[self.position].0;
+	}
+
+	fn column(&self) -> usize {
+		return self.tokens[self.position].1;
+	}
+
+	fn at(&self, column: usize, result: Result<Value, CalcError>) -> Result<Value, CalcError> {
+		return result.map_err(|error| CalcError {
+			column: error.column.or(Some(column)),
+			..error
+		});
+	}
+
+	fn expect(&mut self, operator: char) -> Result<(), CalcError> {
+		if *self.peek() != Token::Operator(operator) {
+			return Err(CalcError {
+				kind: ErrorKind::Invalid,
+				message: format!("expected '{}'", operator),
+				column: Some(self.column()),
+			});
+		}
+		self.position += 1;
+
+		return Ok(());
+	}
+
+	// expression := term (('+' | '-') term)*
+
+	fn expression(&mut self) -> Result<Value, CalcError> {
+		let mut result = self.term()?;
+
+		while let Token::Operator(op @ ('+' | '-')) = *self.peek() {
+			let column = self.column();
+
+			self.position += 1;
+
+			let rhs = self.term()?;
+
+			result = match op {
+				'+' => self.at(column, result.zip(&rhs, "add", |a, b| a + b))?,
+				_ => self.at(column, result.zip(&rhs, "subtract", |a, b| a - b))?,
+			};
+		}
+
+		return Ok(result);
+	}
+
+	// term := unary (('*' | '/') unary)*
+
+	fn term(&mut self) -> Result<Value, CalcError> {
+		let mut result = self.unary()?;
+
+		while let Token::Operator(op @ ('*' | '/')) = *self.peek() {
+			let column = self.column();
+
+			self.position += 1;
+
+			let rhs = self.unary()?;
+
+			result = match (op, &rhs) {
+				('*', _) => self.at(column, result.mul(&rhs))?,
+				(_, Value::Scalar(d)) => result.map(|x| x / d),
+				(_, other) => {
+					return self.at(
+						column,
+						error(format!("cannot divide by a {}", other.kind())),
+					)
+				}
+			};
+		}
+
+		return Ok(result);
+	}
+
+	fn unary(&mut self) -> Result<Value, CalcError> {
+		if *self.peek() == Token::Operator('-') {
+			self.position += 1;
+
+			return Ok(self.unary()?.map(|x| -x));
+		}
+
+		return self.primary();
+	}
+
+	fn primary(&mut self) -> Result<Value, CalcError> {
+		let column = self.column();
+		let token = self.peek().clone();
+
+		self.position += 1;
+
+		match token {
+			Token::Number(x) => Ok(Value::Scalar(x)),
+			Token::Literal(text) => {
+				let rows = parse_rows::<f32>(&text).map_err(|e| CalcError {
+					kind: ErrorKind::Invalid,
+					message: e.kind.to_string(),
+					column: Some(column + e.column - 1),
+				})?;
+
+				if rows.len() == 1 {
+					return Ok(Value::Vector(rows[0].clone()));
+				}
+
+				Ok(Value::Matrix {
+					rows: rows.len(),
+					cols: rows[0].len(),
+					data: rows.concat(),
+				})
+			}
+			Token::Name(name) if *self.peek() == Token::Operator('(') => {
+				self.position += 1;
+
+				let mut args = Vec::new();
+
+				if *self.peek() != Token::Operator(')') {
+					args.push(self.expression()?);
+					while *self.peek() == Token::Operator(',') {
+						self.position += 1;
+						args.push(self.expression()?);
+					}
+				}
+				self.expect(')')?;
+
+				self.at(column, call(&name, &args))
+			}
+			Token::Name(name) => match self.variables.get(&name) {
+				Some(value) => Ok(value.clone()),
+				None => self.at(column, error(format!("unknown variable '{}'", name))),
+			},
+			Token::Operator('(') => {
+				let result = self.expression()?;
+
+				self.expect(')')?;
+
+				Ok(result)
+			}
+			Token::End => {
+				self.position -= 1;
+				self.at(column, error("unexpected end of input"))
+			}
+			Token::Operator(c) => self.at(column, error(format!("unexpected '{}'", c))),
+		}
+	}
+}
+
+pub struct Calculator {
+	variables: HashMap<String, Value>,
+	history: Vec<String>,
+}
+
+impl Calculator {
+	pub fn new() -> Self {
+		return Calculator {
+			variables: HashMap::new(),
+			history: Vec::new(),
+		};
+	}
+
+	pub fn history(&self) -> &[String] {
+		return &self.history;
+	}
+
+	pub fn variables(&self) -> Vec<(&String, &Value)> {
+		let mut result: Vec<(&String, &Value)> = self.variables.iter().collect();
+
+		result.sort_by_key(|(name, _)| *name);
+
+		return result;
+	}
+
+	pub fn get(&self, name: &str) -> Option<&Value> {
+		return self.variables.get(name);
+	}
+
+	// Matrices have to hold exactly rows * cols values
+
+	pub fn set(&mut self, name: &str, value: Value) -> Result<(), CalcError> {
+		if let Value::Matrix { rows, cols, data } = &value {
+			if rows.checked_mul(*cols) != Some(data.len()) {
+				return error(format!(
+					"a {}x{} matrix needs {} values, got {}",
+					rows,
+					cols,
+					rows.saturating_mul(*cols),
+					data.len()
+				));
+			}
+		}
+		self.variables.insert(name.to_string(), value);
+
+		return Ok(());
+	}
+
+	// `name = expression` or a bare expression, whose value also goes into
+	// `ans`. Blank lines give None.
+
+	pub fn eval(&mut self, line: &str) -> Result<Option<Value>, CalcError> {
+		let line = line.trim();
+
+		if line.is_empty() {
+			return Ok(None);
+		}
+		self.history.push(line.to_string());
+
+		let tokens = tokenize(line)?;
+		let target = match (&tokens[0].0, &tokens[1].0) {
+			(Token::Name(name), Token::Operator('=')) => Some(name.clone()),
+			_ => None,
+		};
+
+		if let Some(name) = &target {
+			if FUNCTIONS.iter().any(|(function, _)| function == name) {
+				return error(format!("'{}' is a function name", name));
+			}
+		}
+
+		let mut parser = Parser {
+			tokens,
+			position: if target.is_some() { 2 } else { 0 },
+			variables: &self.variables,
+		};
+		let value = parser.expression()?;
+
+		if *parser.peek() != Token::End {
+			return Err(CalcError {
+				kind: ErrorKind::Invalid,
+				message: String::from("unexpected input after the expression"),
+				column: Some(parser.column()),
+			});
+		}
+
+		self.variables
+			.insert(target.unwrap_or_else(|| String::from("ans")), value.clone());
+
+		return Ok(Some(value));
+	}
+}
+
+impl Default for Calculator {
+	fn default() -> Self {
+		return Calculator::new();
+	}
+}
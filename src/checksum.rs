@@ -0,0 +1,48 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   checksum.rs                                        :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:17:22 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:17:22 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// CRC-32 (IEEE 802.3, reflected polynomial 0xEDB88320) as used by zip
+
+const fn table() -> [u32; 256] {
+	let mut table = [0u32; 256];
+	let mut i = 0;
+
+	while i < 256 {
+		let mut crc = i as u32;
+		let mut bit = 0;
+
+		while bit < 8 {
+			crc = if crc & 1 != 0 {
+				(crc >> 1) ^ 0xEDB8_8320
+			} else {
+				crc >> 1
+			};
+			bit += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+
+	return table;
+}
+
+static TABLE: [u32; 256] = table();
+
+pub fn crc32(data: &[u8]) -> u32 {
+	let mut crc = !0u32;
+
+	for &byte in data {
+		crc = TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+	}
+
+	return !crc;
+}
@@ -0,0 +1,527 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   cli.rs                                             :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:25:34 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:08:20 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::binary::{encode_matrix, encode_vector, View};
+use crate::calculator::{CalcError, Calculator, ErrorKind, Value};
+use crate::file::{
+	read_csv, read_matrix_market, write_csv, write_matrix_market_array, CsvOptions, MAX_ARRAY,
+};
+use crate::npy::{read_npy, write_npy, NpyArray, NpyOptions};
+use crate::parse::parse_rows;
+
+// Batch mode of the matrix binary: one command on files, the result on
+// stdout or in an output file, and an exit code for scripts
+
+pub const USAGE: &str = "\
+Usage: matrix [command] [files] [options]
+
+Commands:
+  det FILE              determinant
+  inv FILE              inverse
+  rank FILE             rank
+  rref FILE             reduced row echelon form
+  trace FILE            trace
+  transpose FILE        transpose
+  solve A_FILE B_FILE   unique solution of A x = b
+  repl                  interactive calculator (also without arguments)
+
+Options:
+  -o, --output FILE     write the result to FILE, format from its extension
+  --format FORMAT       input format: csv, mtx, npy, bin or txt
+  --json                print the result or the error as JSON
+
+Input formats are detected from the content, then from the extension.
+Exit codes: 0 success, 1 usage, 2 invalid input, 3 singular matrix or
+system without a unique solution.";
+
+pub const USAGE_ERROR: u8 = 1;
+pub const INPUT_ERROR: u8 = 2;
+pub const SINGULAR_ERROR: u8 = 3;
+
+// read_matrix_market bounds array files before reading them. Coordinate
+// files only hold their entries, so the dense size is checked here.
+
+const MAX_DENSE: usize = MAX_ARRAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+	Csv(char),
+	MatrixMarket,
+	Npy,
+	Binary,
+	Text,
+}
+
+#[derive(Debug)]
+pub(crate) struct Failure {
+	pub code: u8,
+	pub message: String,
+}
+
+fn failure(code: u8, message: impl Into<String>) -> Failure {
+	return Failure {
+		code,
+		message: message.into(),
+	};
+}
+
+fn extension(path: &str) -> String {
+	return Path::new(path)
+		.extension()
+		.map(|ext| ext.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+}
+
+fn delimiter(text: &str) -> Option<char> {
+	let line = text.lines().find(|line| !line.trim().is_empty())?;
+
+	if line.contains('[') {
+		return None;
+	}
+
+	return [',', ';', '\t'].into_iter().find(|&c| line.contains(c));
+}
+
+// Magic bytes first, then the extension, then a look at the text
+
+pub(crate) fn detect(path: &str, bytes: &[u8]) -> Format {
+	if bytes.starts_with(b"\x93NUMPY") {
+		return Format::Npy;
+	}
+	if bytes.starts_with(b"MTRX") {
+		return Format::Binary;
+	}
+	if bytes.starts_with(b"%%MatrixMarket") {
+		return Format::MatrixMarket;
+	}
+
+	let text = String::from_utf8_lossy(bytes);
+
+	return match extension(path).as_str() {
+		"mtx" => Format::MatrixMarket,
+		"npy" => Format::Npy,
+		"bin" => Format::Binary,
+		"csv" => Format::Csv(delimiter(&text).unwrap_or(',')),
+		"tsv" => Format::Csv('\t'),
+		_ => delimiter(&text).map_or(Format::Text, Format::Csv),
+	};
+}
+
+fn parse_format(name: &str) -> Result<Format, Failure> {
+	return match name {
+		"csv" => Ok(Format::Csv(',')),
+		"tsv" => Ok(Format::Csv('\t')),
+		"mtx" => Ok(Format::MatrixMarket),
+		"npy" => Ok(Format::Npy),
+		"bin" => Ok(Format::Binary),
+		"txt" => Ok(Format::Text),
+		other => Err(failure(USAGE_ERROR, format!("unknown format '{}'", other))),
+	};
+}
+
+fn matrix_value(rows: Vec<Vec<f32>>) -> Value {
+	return Value::Matrix {
+		rows: rows.len(),
+		cols: rows.first().map_or(0, |row| row.len()),
+		data: rows.concat(),
+	};
+}
+
+fn load(path: &str, format: Option<Format>) -> Result<Value, Failure> {
+	let bytes = fs::read(path).map_err(|e| failure(INPUT_ERROR, format!("{}: {}", path, e)))?;
+	let invalid = |message: String| failure(INPUT_ERROR, format!("{}: {}", path, message));
+
+	let value = match format.unwrap_or_else(|| detect(path, &bytes)) {
+		Format::Csv(delimiter) => {
+			// A first line with a non-empty field that is not a number is a
+			// header. Empty fields are left for read_csv to report.
+
+			let text = String::from_utf8_lossy(&bytes);
+			let first = text
+				.lines()
+				.find(|line| !line.trim().is_empty())
+				.unwrap_or("");
+			let has_header = first
+				.split(delimiter)
+				.map(|field| field.trim().trim_matches('"'))
+				.any(|field| !field.is_empty() && field.parse::<f32>().is_err());
+			let options = CsvOptions {
+				delimiter,
+				has_header,
+			};
+			let rows =
+				read_csv::<f32, _>(&bytes[..], &options).map_err(|e| invalid(e.to_string()))?;
+
+			Ok(matrix_value(rows))
+		}
+		Format::MatrixMarket => {
+			let coo =
+				read_matrix_market::<f32, _>(&bytes[..]).map_err(|e| invalid(e.to_string()))?;
+			let size = coo
+				.rows
+				.checked_mul(coo.cols)
+				.filter(|&size| size <= MAX_DENSE);
+			let Some(size) = size else {
+				return Err(invalid(format!(
+					"{}x{} is too large to load as a dense matrix",
+					coo.rows, coo.cols
+				)));
+			};
+			let mut data = vec![0.; size];
+
+			for &(i, j, value) in &coo.entries {
+				data[i * coo.cols + j] += value;
+			}
+
+			Ok(Value::Matrix {
+				rows: coo.rows,
+				cols: coo.cols,
+				data,
+			})
+		}
+		Format::Npy => {
+			let array = read_npy::<f32, _>(&bytes[..]).map_err(|e| invalid(e.to_string()))?;
+
+			match array.shape[..] {
+				[_] => Ok(Value::Vector(array.data)),
+				[rows, cols] => Ok(Value::Matrix {
+					rows,
+					cols,
+					data: array.data,
+				}),
+				_ => Err(invalid(format!(
+					"{}-dimensional arrays are not supported",
+					array.shape.len()
+				))),
+			}
+		}
+		Format::Binary => {
+			let view = View::<f32>::parse(&bytes).map_err(|e| invalid(e.to_string()))?;
+			let data: Vec<f32> = view.iter().collect();
+
+			match view.is_matrix() {
+				true => Ok(Value::Matrix {
+					rows: view.rows,
+					cols: view.cols,
+					data,
+				}),
+				false => Ok(Value::Vector(data)),
+			}
+		}
+		Format::Text => {
+			let text = String::from_utf8_lossy(&bytes);
+			let rows = parse_rows::<f32>(&text).map_err(|e| invalid(e.to_string()))?;
+
+			Ok(matrix_value(rows))
+		}
+	}?;
+
+	return match value {
+		Value::Matrix { rows, cols, .. } if rows == 0 || cols == 0 => {
+			Err(invalid(format!("empty {}x{} matrix", rows, cols)))
+		}
+		Value::Vector(v) if v.is_empty() => Err(invalid(String::from("empty vector"))),
+		value => Ok(value),
+	};
+}
+
+fn vector(value: Value, path: &str) -> Result<Value, Failure> {
+	return match value {
+		Value::Matrix { rows, cols, data } if rows == 1 || cols == 1 => Ok(Value::Vector(data)),
+		Value::Vector(v) => Ok(Value::Vector(v)),
+		other => Err(failure(
+			INPUT_ERROR,
+			format!(
+				"{}: expected a single row or column, got a {}",
+				path,
+				other.kind()
+			),
+		)),
+	};
+}
+
+fn save(path: &str, value: &Value) -> Result<(), Failure> {
+	let (rows, cols, data) = match value {
+		Value::Matrix { rows, cols, data } => (*rows, *cols, &data[..]),
+		Value::Vector(v) => (v.len(), 1, &v[..]),
+		Value::Scalar(x) => {
+			if extension(path) != "txt" {
+				return Err(failure(
+					USAGE_ERROR,
+					"a scalar result can only be written to a .txt file",
+				));
+			}
+			return fs::write(path, format!("{}\n", x))
+				.map_err(|e| failure(INPUT_ERROR, e.to_string()));
+		}
+	};
+	let lines: Vec<&[f32]> = data.chunks(cols.max(1)).collect();
+	let mut out = Vec::new();
+	let result = match extension(path).as_str() {
+		"csv" => write_csv(&mut out, &lines, &CsvOptions::default()).map_err(|e| e.to_string()),
+		"tsv" => {
+			let options = CsvOptions {
+				delimiter: '\t',
+				..CsvOptions::default()
+			};
+
+			write_csv(&mut out, &lines, &options).map_err(|e| e.to_string())
+		}
+		"mtx" => write_matrix_market_array(&mut out, &lines).map_err(|e| e.to_string()),
+		"npy" => {
+			let shape = match value {
+				Value::Vector(_) => vec![rows],
+				_ => vec![rows, cols],
+			};
+			let array = NpyArray {
+				shape,
+				data: data.to_vec(),
+			};
+
+			write_npy(&mut out, &array, &NpyOptions::default()).map_err(|e| e.to_string())
+		}
+		"bin" => {
+			out = match value {
+				Value::Vector(v) => encode_vector(v),
+				_ => encode_matrix(rows, cols, data),
+			};
+			Ok(())
+		}
+		_ => {
+			for line in &lines {
+				let values: Vec<String> = line.iter().map(|x| x.to_string()).collect();
+
+				writeln!(out, "{}", values.join(" ")).ok();
+			}
+			Ok(())
+		}
+	};
+
+	result.map_err(|e| failure(INPUT_ERROR, e))?;
+
+	return fs::write(path, out).map_err(|e| failure(INPUT_ERROR, format!("{}: {}", path, e)));
+}
+
+fn json_number(x: f32) -> String {
+	if x.is_finite() {
+		return x.to_string();
+	}
+	return String::from("null");
+}
+
+fn json_string(s: &str) -> String {
+	let mut result = String::from("\"");
+
+	for c in s.chars() {
+		match c {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+			c => result.push(c),
+		}
+	}
+	result.push('"');
+
+	return result;
+}
+
+fn json_value(value: &Value) -> String {
+	let list = |values: &[f32]| {
+		let values: Vec<String> = values.iter().map(|&x| json_number(x)).collect();
+		format!("[{}]", values.join(", "))
+	};
+
+	return match value {
+		Value::Scalar(x) => json_number(*x),
+		Value::Vector(v) => list(v),
+		Value::Matrix { cols, data, .. } => {
+			let rows: Vec<String> = data.chunks((*cols).max(1)).map(list).collect();
+			format!("[{}]", rows.join(", "))
+		}
+	};
+}
+
+#[derive(Debug)]
+pub(crate) struct Options {
+	pub command: String,
+	pub files: Vec<String>,
+	pub output: Option<String>,
+	pub format: Option<Format>,
+	pub json: bool,
+}
+
+pub(crate) fn parse_args(args: &[String]) -> Result<Options, Failure> {
+	let mut options = Options {
+		command: args[0].clone(),
+		files: Vec::new(),
+		output: None,
+		format: None,
+		json: false,
+	};
+	let mut args = args[1..].iter();
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"-o" | "--output" => match args.next() {
+				Some(path) => options.output = Some(path.clone()),
+				None => return Err(failure(USAGE_ERROR, format!("{} needs a file name", arg))),
+			},
+			"--format" => match args.next() {
+				Some(name) => options.format = Some(parse_format(name)?),
+				None => return Err(failure(USAGE_ERROR, "--format needs a format name")),
+			},
+			"--json" => options.json = true,
+			flag if flag.starts_with('-') && flag.len() > 1 => {
+				return Err(failure(USAGE_ERROR, format!("unknown option '{}'", flag)))
+			}
+			file => options.files.push(file.to_string()),
+		}
+	}
+
+	return Ok(options);
+}
+
+pub(crate) fn execute(options: &Options) -> Result<Value, Failure> {
+	let arity = match options.command.as_str() {
+		"det" | "inv" | "rank" | "rref" | "trace" | "transpose" => 1,
+		"solve" => 2,
+		other => return Err(failure(USAGE_ERROR, format!("unknown command '{}'", other))),
+	};
+
+	if options.files.len() != arity {
+		return Err(failure(
+			USAGE_ERROR,
+			format!(
+				"{} takes {} file(s), got {}",
+				options.command,
+				arity,
+				options.files.len()
+			),
+		));
+	}
+
+	let mut calculator = Calculator::new();
+	let calc_failure = |e: CalcError| {
+		let code = match e.kind {
+			ErrorKind::Singular => SINGULAR_ERROR,
+			ErrorKind::Invalid => INPUT_ERROR,
+		};
+
+		failure(code, e.message)
+	};
+
+	calculator
+		.set("a", load(&options.files[0], options.format)?)
+		.map_err(calc_failure)?;
+	if arity == 2 {
+		let b = load(&options.files[1], options.format)?;
+
+		calculator
+			.set("b", vector(b, &options.files[1])?)
+			.map_err(calc_failure)?;
+	}
+
+	let expression = match arity {
+		1 => format!("{}(a)", options.command),
+		_ => format!("{}(a, b)", options.command),
+	};
+	let value = calculator.eval(&expression).map_err(calc_failure)?;
+
+	// Only blank lines evaluate to nothing
+	let value = value.unwrap();
+
+	if let Some(path) = &options.output {
+		save(path, &value)?;
+	}
+
+	return Ok(value);
+}
+
+// What a command line prints on stdout and stderr, and its exit code
+
+#[derive(Debug)]
+pub(crate) struct Report {
+	pub stdout: String,
+	pub stderr: String,
+	pub code: u8,
+}
+
+pub(crate) fn report(args: &[String]) -> Report {
+	let mut report = Report {
+		stdout: String::new(),
+		stderr: String::new(),
+		code: 0,
+	};
+
+	if matches!(args[0].as_str(), "help" | "-h" | "--help") {
+		report.stdout = format!("{}\n", USAGE);
+		return report;
+	}
+
+	let json = args.iter().any(|arg| arg == "--json");
+	let result = parse_args(args).and_then(|options| {
+		let value = execute(&options)?;
+
+		Ok((options, value))
+	});
+
+	match result {
+		Ok((options, value)) if options.json => {
+			let output = match &options.output {
+				Some(path) => format!(", \"output\": {}", json_string(path)),
+				None => String::new(),
+			};
+
+			report.stdout = format!(
+				"{{\"command\": {}, \"result\": {}{}}}\n",
+				json_string(&options.command),
+				json_value(&value),
+				output
+			);
+		}
+		Ok((options, _)) if options.output.is_some() => {}
+		Ok((_, value)) => report.stdout = format!("{}\n", value.to_string().trim_end()),
+		Err(error) if json => {
+			report.stdout = format!(
+				"{{\"command\": {}, \"error\": {}, \"code\": {}}}\n",
+				json_string(&args[0]),
+				json_string(&error.message),
+				error.code
+			);
+			report.code = error.code;
+		}
+		Err(error) => {
+			report.stderr = format!("Error: {}\n", error.message);
+			if error.code == USAGE_ERROR {
+				report.stderr.push_str("Run 'matrix help' for usage\n");
+			}
+			report.code = error.code;
+		}
+	}
+
+	return report;
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+	let report = report(args);
+
+	print!("{}", report.stdout);
+	eprint!("{}", report.stderr);
+
+	return ExitCode::from(report.code);
+}
@@ -0,0 +1,606 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   elimination.rs                                     :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:07:56 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:09:01 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::fmt;
+
+use crate::matrix::Matrix;
+use crate::parallel;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowOperation<K> {
+	Swap(usize, usize),
+	Scale {
+		row: usize,
+		factor: K,
+	},
+	AddMultiple {
+		target: usize,
+		source: usize,
+		factor: K,
+	},
+	// Entries of column `col` within the tolerance, set to zero
+	Clear {
+		col: usize,
+		rows: Vec<usize>,
+	},
+}
+
+// Rows are numbered from 1 when rendered, as on paper
+
+impl<K: Traits> fmt::Display for RowOperation<K> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			RowOperation::Swap(a, b) => write!(f, "R{} <-> R{}", a + 1, b + 1),
+			RowOperation::Scale { row, factor } => {
+				write!(f, "R{} <- {} R{}", row + 1, (*factor).into(), row + 1)
+			}
+			RowOperation::AddMultiple {
+				target,
+				source,
+				factor,
+			} => {
+				let factor: f32 = (*factor).into();
+				let sign = if factor < 0. { '-' } else { '+' };
+
+				write!(
+					f,
+					"R{} <- R{} {} {} R{}",
+					target + 1,
+					target + 1,
+					sign,
+					factor.abs(),
+					source + 1
+				)
+			}
+			RowOperation::Clear { col, rows } => {
+				let rows: Vec<String> = rows.iter().map(|i| format!("R{}", i + 1)).collect();
+
+				write!(f, "C{} <- 0 in {}", col + 1, rows.join(", "))
+			}
+		}
+	}
+}
+
+impl<K: Traits> RowOperation<K> {
+	pub fn to_latex(&self) -> String {
+		match self {
+			RowOperation::Swap(a, b) => format!("R_{{{}}} \\leftrightarrow R_{{{}}}", a + 1, b + 1),
+			RowOperation::Scale { row, factor } => {
+				format!(
+					"R_{{{}}} \\leftarrow {} R_{{{}}}",
+					row + 1,
+					(*factor).into(),
+					row + 1
+				)
+			}
+			RowOperation::AddMultiple {
+				target,
+				source,
+				factor,
+			} => {
+				let factor: f32 = (*factor).into();
+				let sign = if factor < 0. { '-' } else { '+' };
+
+				format!(
+					"R_{{{}}} \\leftarrow R_{{{}}} {} {} R_{{{}}}",
+					target + 1,
+					target + 1,
+					sign,
+					factor.abs(),
+					source + 1
+				)
+			}
+			RowOperation::Clear { col, rows } => {
+				let rows: Vec<String> = rows.iter().map(|i| format!("R_{{{}}}", i + 1)).collect();
+
+				format!(
+					"C_{{{}}} \\leftarrow 0 \\text{{ in }} {}",
+					col + 1,
+					rows.join(", ")
+				)
+			}
+		}
+	}
+}
+
+// Receives every elementary row operation of an elimination together with
+// the matrix it produced
+
+pub trait Observer<K> {
+	fn start(&mut self, _matrix: &[&[K]], _pivot_limit: usize) {}
+
+	fn step(&mut self, operation: &RowOperation<K>, matrix: &[&[K]]);
+
+	fn pivot(&mut self, _row: usize, _col: usize) {}
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Solution<K, const N: usize> {
+	Unique(Vector<K, N>),
+	Infinite {
+		particular: Vector<K, N>,
+		null_space: Vec<Vector<K, N>>,
+	},
+	Inconsistent,
+}
+
+// Entries below this are rounding noise left by earlier eliminations. The
+// default pivot tolerance for eliminate.
+
+pub fn tolerance<K, R>(rows: &[R]) -> f32
+where
+	K: Traits,
+	R: AsRef<[K]>,
+{
+	let mut max: f32 = 0.;
+	let mut n = 0;
+
+	for row in rows {
+		for x in row.as_ref() {
+			max = max.max((*x).into().abs());
+		}
+		n = n.max(row.as_ref().len());
+	}
+
+	return max * f32::EPSILON * rows.len().max(n) as f32;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Form {
+	Echelon,
+	Reduced,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EliminationRecord<K> {
+	pub pivots: Vec<(usize, usize)>,
+	pub operations: Vec<RowOperation<K>>,
+}
+
+impl<K: Traits> EliminationRecord<K> {
+	pub fn new() -> Self {
+		return EliminationRecord {
+			pivots: Vec::new(),
+			operations: Vec::new(),
+		};
+	}
+
+	pub fn rank(&self) -> usize {
+		return self.pivots.len();
+	}
+
+	pub fn swaps(&self) -> Vec<(usize, usize)> {
+		return self
+			.operations
+			.iter()
+			.filter_map(|op| match op {
+				RowOperation::Swap(a, b) => Some((*a, *b)),
+				_ => None,
+			})
+			.collect();
+	}
+
+	// (target, source, l) for each `row target -= l * row source`
+
+	pub fn multipliers(&self) -> Vec<(usize, usize, K)> {
+		return self
+			.operations
+			.iter()
+			.filter_map(|op| match op {
+				RowOperation::AddMultiple {
+					target,
+					source,
+					factor,
+				} => Some((*target, *source, -*factor)),
+				_ => None,
+			})
+			.collect();
+	}
+}
+
+impl<K: Traits> Default for EliminationRecord<K> {
+	fn default() -> Self {
+		return EliminationRecord::new();
+	}
+}
+
+impl<K: Traits> Observer<K> for EliminationRecord<K> {
+	fn step(&mut self, operation: &RowOperation<K>, _matrix: &[&[K]]) {
+		self.operations.push(operation.clone());
+	}
+
+	fn pivot(&mut self, row: usize, col: usize) {
+		self.pivots.push((row, col));
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<K> {
+	pub operation: RowOperation<K>,
+	pub matrix: Vec<Vec<K>>,
+}
+
+// Every intermediate matrix of an elimination, for following it by hand.
+// `split` is the first augmented column, drawn behind a bar.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace<K> {
+	pub initial: Vec<Vec<K>>,
+	pub steps: Vec<Step<K>>,
+	pub split: Option<usize>,
+}
+
+impl<K: Traits> Trace<K> {
+	pub fn new() -> Self {
+		return Trace {
+			initial: Vec::new(),
+			steps: Vec::new(),
+			split: None,
+		};
+	}
+
+	pub fn to_text(&self) -> String {
+		let mut result = String::from("Initial matrix\n");
+
+		result.push_str(&text_matrix(&self.initial, self.split));
+		for step in &self.steps {
+			result.push('\n');
+			result.push_str(&step.operation.to_string());
+			result.push('\n');
+			result.push_str(&text_matrix(&step.matrix, self.split));
+		}
+
+		return result;
+	}
+
+	pub fn to_latex(&self) -> String {
+		let mut result = latex_matrix(&self.initial, self.split);
+
+		for step in &self.steps {
+			result.push_str(&format!("\\xrightarrow{{{}}}\n", step.operation.to_latex()));
+			result.push_str(&latex_matrix(&step.matrix, self.split));
+		}
+
+		return result;
+	}
+}
+
+impl<K: Traits> Default for Trace<K> {
+	fn default() -> Self {
+		return Trace::new();
+	}
+}
+
+impl<K: Traits> Observer<K> for Trace<K> {
+	fn start(&mut self, matrix: &[&[K]], pivot_limit: usize) {
+		self.initial = matrix.iter().map(|row| row.to_vec()).collect();
+		self.steps.clear();
+		self.split = matrix
+			.first()
+			.filter(|row| pivot_limit < row.len())
+			.map(|_| pivot_limit);
+	}
+
+	fn step(&mut self, operation: &RowOperation<K>, matrix: &[&[K]]) {
+		self.steps.push(Step {
+			operation: operation.clone(),
+			matrix: matrix.iter().map(|row| row.to_vec()).collect(),
+		});
+	}
+}
+
+fn text_matrix<K: Traits>(matrix: &[Vec<K>], split: Option<usize>) -> String {
+	let cells: Vec<Vec<String>> = matrix
+		.iter()
+		.map(|row| row.iter().map(|&x| format!("{}", x.into())).collect())
+		.collect();
+	let cols = cells.first().map_or(0, |row| row.len());
+	let widths: Vec<usize> = (0..cols)
+		.map(|j| cells.iter().map(|row| row[j].len()).max().unwrap_or(0))
+		.collect();
+	let mut result = String::new();
+
+	for row in &cells {
+		result.push('[');
+		for j in 0..cols {
+			if split == Some(j) {
+				result.push_str(" |");
+			}
+			result.push_str(&format!(" {:>width$}", row[j], width = widths[j]));
+		}
+		result.push_str(" ]\n");
+	}
+
+	return result;
+}
+
+fn latex_matrix<K: Traits>(matrix: &[Vec<K>], split: Option<usize>) -> String {
+	let cols = matrix.first().map_or(0, |row| row.len());
+	let rows: Vec<String> = matrix
+		.iter()
+		.map(|row| {
+			let cells: Vec<String> = row.iter().map(|&x| format!("{}", x.into())).collect();
+			cells.join(" & ")
+		})
+		.collect();
+
+	let (begin, end) = match split {
+		Some(split) => (
+			format!(
+				"\\left[\\begin{{array}}{{{}|{}}}",
+				"c".repeat(split),
+				"c".repeat(cols - split)
+			),
+			String::from("\\end{array}\\right]"),
+		),
+		None => (
+			String::from("\\begin{bmatrix}"),
+			String::from("\\end{bmatrix}"),
+		),
+	};
+
+	return format!("{}\n{}\n{}\n", begin, rows.join(" \\\\\n"), end);
+}
+
+// Gaussian elimination with partial pivoting on any row storage. The
+// reduced form also normalizes the pivots and clears above them.
+// Only the first `pivot_limit` columns can hold pivots, the others (the
+// right-hand side of an augmented matrix) are just carried along.
+// Candidate pivots whose absolute value is at most `tolerance` count as
+// zero, 0 keeps the exact comparison.
+
+pub fn eliminate<K, R>(
+	rows: &mut [R],
+	pivot_limit: usize,
+	form: Form,
+	tolerance: f32,
+	mut observer: Option<&mut dyn Observer<K>>,
+) where
+	K: Traits,
+	R: AsRef<[K]> + AsMut<[K]> + Send,
+{
+	if rows.is_empty() {
+		return;
+	}
+
+	let m = rows.len();
+	let n = rows[0].as_ref().len();
+
+	if let Some(observer) = observer.as_deref_mut() {
+		observer.start(&snapshot(rows), pivot_limit);
+	}
+
+	let mut pivot_row = 0;
+
+	for col in 0..pivot_limit.min(n) {
+		if pivot_row >= m {
+			break;
+		}
+
+		// Get the max row and swap
+
+		let mut max_row = pivot_row;
+		for i in pivot_row + 1..m {
+			if rows[i].as_ref()[col].into().abs() > rows[max_row].as_ref()[col].into().abs() {
+				max_row = i;
+			}
+		}
+
+		if max_row != pivot_row {
+			rows.swap(pivot_row, max_row);
+			if let Some(observer) = observer.as_deref_mut() {
+				observer.step(&RowOperation::Swap(pivot_row, max_row), &snapshot(rows));
+			}
+		}
+
+		if rows[pivot_row].as_ref()[col].into().abs() <= tolerance {
+			let noise: Vec<usize> = (pivot_row..m)
+				.filter(|&i| rows[i].as_ref()[col] != K::default())
+				.collect();
+
+			for &i in &noise {
+				rows[i].as_mut()[col] = K::default();
+			}
+			if let Some(observer) = observer.as_deref_mut().filter(|_| !noise.is_empty()) {
+				let operation = RowOperation::Clear { col, rows: noise };
+
+				observer.step(&operation, &snapshot(rows));
+			}
+			continue;
+		}
+
+		if let Some(observer) = observer.as_deref_mut() {
+			observer.pivot(pivot_row, col);
+		}
+
+		let pivot = rows[pivot_row].as_ref()[col];
+
+		// Normalize the pivot row
+
+		if form == Form::Reduced {
+			for j in col..n {
+				rows[pivot_row].as_mut()[j] /= pivot;
+			}
+			if let Some(observer) = observer.as_deref_mut() {
+				let operation = RowOperation::Scale {
+					row: pivot_row,
+					factor: K::from(1.) / pivot,
+				};
+
+				observer.step(&operation, &snapshot(rows));
+			}
+		}
+
+		// Cancel the elements
+
+		let pivot = rows[pivot_row].as_ref()[col];
+		let pivot_values = rows[pivot_row].as_ref().to_vec();
+		let first = match form {
+			Form::Echelon => pivot_row + 1,
+			Form::Reduced => 0,
+		};
+		let cancel = |row: &mut [K]| {
+			let factor = match form {
+				Form::Echelon => row[col] / pivot,
+				Form::Reduced => row[col],
+			};
+
+			for j in col..n {
+				row[j] = (-factor).mul_add(pivot_values[j], row[j]);
+			}
+			row[col] = K::default();
+
+			return factor;
+		};
+
+		// One operation at a time while observed, so that each one comes
+		// with its own intermediate matrix
+
+		match observer.as_deref_mut() {
+			Some(observer) => {
+				for i in first..m {
+					if i == pivot_row || rows[i].as_ref()[col] == K::default() {
+						continue;
+					}
+
+					let factor = cancel(rows[i].as_mut());
+					let operation = RowOperation::AddMultiple {
+						target: i,
+						source: pivot_row,
+						factor: -factor,
+					};
+
+					observer.step(&operation, &snapshot(rows));
+				}
+			}
+			None => {
+				parallel::for_each_row(&mut rows[first..], n, |i, row| {
+					if first + i != pivot_row {
+						cancel(row.as_mut());
+					}
+				});
+			}
+		}
+
+		pivot_row += 1;
+	}
+}
+
+fn snapshot<K, R: AsRef<[K]>>(rows: &[R]) -> Vec<&[K]> {
+	return rows.iter().map(|row| row.as_ref()).collect();
+}
+
+// Solves from the rows of an augmented matrix [A | b]: None when the
+// system is inconsistent, else a particular solution (free variables set
+// to zero) and a basis of the null space of A, empty when it is unique
+
+pub fn solve_augmented<K: Traits>(
+	mut augmented: Vec<Vec<K>>,
+	record: Option<&mut Vec<RowOperation<K>>>,
+) -> Option<(Vec<K>, Vec<Vec<K>>)> {
+	let n = augmented.first().map_or(1, |row| row.len()) - 1;
+
+	// Pivots are judged against A alone and the 0 = c check against b, so
+	// neither side's scale hides the other
+
+	let coefficients: Vec<&[K]> = augmented.iter().map(|row| &row[..n]).collect();
+	let tolerance = tolerance(&coefficients);
+	let rhs = augmented
+		.iter()
+		.fold(0_f32, |max, row| max.max(row[n].into().abs()))
+		* f32::EPSILON
+		* augmented.len().max(n + 1) as f32;
+	let mut steps = EliminationRecord::new();
+
+	eliminate(
+		&mut augmented,
+		n,
+		Form::Reduced,
+		tolerance,
+		Some(&mut steps),
+	);
+
+	let pivots: Vec<usize> = steps.pivots.iter().map(|&(_, col)| col).collect();
+
+	if let Some(record) = record {
+		*record = steps.operations;
+	}
+
+	// A zero row of A with a non-zero right-hand side: 0 = c
+
+	for row in &augmented[pivots.len()..] {
+		if row[n].into().abs() > rhs {
+			return None;
+		}
+	}
+
+	let mut particular = vec![K::default(); n];
+
+	for (row, &pivot) in pivots.iter().enumerate() {
+		particular[pivot] = augmented[row][n];
+	}
+
+	let mut null_space = Vec::new();
+
+	for free in (0..n).filter(|j| !pivots.contains(j)) {
+		let mut vector = vec![K::default(); n];
+
+		vector[free] = K::from(1.);
+		for (row, &pivot) in pivots.iter().enumerate() {
+			vector[pivot] = -augmented[row][free];
+		}
+		null_space.push(vector);
+	}
+
+	return Some((particular, null_space));
+}
+
+impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
+	pub fn solve(&self, b: &Vector<K, M>) -> Solution<K, N> {
+		return self.solve_system(b, None);
+	}
+
+	pub fn solve_with_steps(&self, b: &Vector<K, M>) -> (Solution<K, N>, Vec<RowOperation<K>>) {
+		let mut steps = Vec::new();
+		let solution = self.solve_system(b, Some(&mut steps));
+
+		return (solution, steps);
+	}
+
+	fn solve_system(
+		&self,
+		b: &Vector<K, M>,
+		record: Option<&mut Vec<RowOperation<K>>>,
+	) -> Solution<K, N> {
+		let augmented: Vec<Vec<K>> = (0..M)
+			.map(|i| {
+				let mut row = self.data[i].to_vec();
+				row.push(b.data[i]);
+				row
+			})
+			.collect();
+		let to_vector = |values: Vec<K>| Vector::new(std::array::from_fn(|i| values[i]));
+
+		return match solve_augmented(augmented, record) {
+			None => Solution::Inconsistent,
+			Some((particular, null_space)) if null_space.is_empty() => {
+				Solution::Unique(to_vector(particular))
+			}
+			Some((particular, null_space)) => Solution::Infinite {
+				particular: to_vector(particular),
+				null_space: null_space.into_iter().map(to_vector).collect(),
+			},
+		};
+	}
+}
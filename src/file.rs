@@ -0,0 +1,517 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   file.rs                                            :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:16:29 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:08:06 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::matrix::Matrix;
+use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+use crate::traits::Traits;
+
+// Lines count from 1
+
+#[derive(Debug)]
+pub enum FileError {
+	Io(io::Error),
+	Header {
+		line: usize,
+		message: String,
+	},
+	InvalidNumber {
+		line: usize,
+		value: String,
+	},
+	RaggedRow {
+		line: usize,
+		expected: usize,
+		found: usize,
+	},
+	OutOfBounds {
+		line: usize,
+		row: usize,
+		col: usize,
+	},
+	EntryCount {
+		expected: usize,
+		found: usize,
+	},
+	Dimensions {
+		expected: (usize, usize),
+		found: (usize, usize),
+	},
+	TooLarge {
+		rows: usize,
+		cols: usize,
+	},
+	Unsupported(String),
+	Empty,
+}
+
+impl fmt::Display for FileError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			FileError::Io(error) => write!(f, "{}", error),
+			FileError::Header { line, message } => write!(f, "line {}: {}", line, message),
+			FileError::InvalidNumber { line, value } => {
+				write!(f, "line {}: invalid number '{}'", line, value)
+			}
+			FileError::RaggedRow {
+				line,
+				expected,
+				found,
+			} => write!(f, "line {}: {} values, expected {}", line, found, expected),
+			FileError::OutOfBounds { line, row, col } => {
+				write!(f, "line {}: entry ({}, {}) out of bounds", line, row, col)
+			}
+			FileError::EntryCount { expected, found } => {
+				write!(f, "{} entries, expected {}", found, expected)
+			}
+			FileError::Dimensions { expected, found } => write!(
+				f,
+				"expected a {}x{} matrix, found {}x{}",
+				expected.0, expected.1, found.0, found.1
+			),
+			FileError::TooLarge { rows, cols } => {
+				write!(f, "{}x{} array is too large", rows, cols)
+			}
+			FileError::Unsupported(what) => write!(f, "unsupported {}", what),
+			FileError::Empty => write!(f, "no values"),
+		}
+	}
+}
+
+impl Error for FileError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			FileError::Io(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for FileError {
+	fn from(error: io::Error) -> Self {
+		FileError::Io(error)
+	}
+}
+
+fn number<K: Traits>(value: &str, line: usize) -> Result<K, FileError> {
+	return value
+		.parse::<f32>()
+		.map(K::from)
+		.map_err(|_| FileError::InvalidNumber {
+			line,
+			value: value.to_string(),
+		});
+}
+
+fn dense<K: Traits, const M: usize, const N: usize>(
+	rows: &[Vec<K>],
+) -> Result<Matrix<K, M, N>, FileError> {
+	let found = (rows.len(), rows.first().map_or(0, |row| row.len()));
+
+	if found != (M, N) {
+		return Err(FileError::Dimensions {
+			expected: (M, N),
+			found,
+		});
+	}
+
+	return Ok(Matrix::from_fn(|i, j| rows[i][j]));
+}
+
+pub struct CsvOptions {
+	pub delimiter: char,
+	pub has_header: bool,
+}
+
+impl Default for CsvOptions {
+	fn default() -> Self {
+		Self {
+			delimiter: ',',
+			has_header: false,
+		}
+	}
+}
+
+pub fn read_csv<K: Traits, R: BufRead>(
+	reader: R,
+	options: &CsvOptions,
+) -> Result<Vec<Vec<K>>, FileError> {
+	let mut rows: Vec<Vec<K>> = Vec::new();
+	let mut header = options.has_header;
+
+	for (i, line) in reader.lines().enumerate() {
+		let line = line?;
+
+		if line.trim().is_empty() {
+			continue;
+		}
+		if header {
+			header = false;
+			continue;
+		}
+
+		let row = line
+			.split(options.delimiter)
+			.map(|field| number(field.trim().trim_matches('"'), i + 1))
+			.collect::<Result<Vec<K>, FileError>>()?;
+
+		if let Some(first) = rows.first() {
+			if row.len() != first.len() {
+				return Err(FileError::RaggedRow {
+					line: i + 1,
+					expected: first.len(),
+					found: row.len(),
+				});
+			}
+		}
+		rows.push(row);
+	}
+
+	if rows.is_empty() {
+		return Err(FileError::Empty);
+	}
+
+	return Ok(rows);
+}
+
+pub fn write_csv<K, R, W>(writer: &mut W, rows: &[R], options: &CsvOptions) -> Result<(), FileError>
+where
+	K: Traits,
+	R: AsRef<[K]>,
+	W: Write,
+{
+	let delimiter = options.delimiter.to_string();
+
+	if options.has_header {
+		let cols = rows.first().map_or(0, |row| row.as_ref().len());
+		let names: Vec<String> = (1..=cols).map(|j| format!("c{}", j)).collect();
+
+		writeln!(writer, "{}", names.join(&delimiter))?;
+	}
+	for row in rows {
+		let values: Vec<String> = row
+			.as_ref()
+			.iter()
+			.map(|&x| format!("{}", x.into()))
+			.collect();
+
+		writeln!(writer, "{}", values.join(&delimiter))?;
+	}
+
+	return Ok(());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+	General,
+	Symmetric,
+	SkewSymmetric,
+}
+
+// Array files store every entry, so their size is bounded before reading
+
+pub const MAX_ARRAY: usize = 1 << 26;
+
+// Coordinate and array files end up as triplets, with 0-based indices and
+// the symmetric half already mirrored
+
+pub fn read_matrix_market<K: Traits, R: BufRead>(reader: R) -> Result<CooMatrix<K>, FileError> {
+	let mut lines = reader.lines().enumerate();
+
+	let banner = match lines.next() {
+		Some((_, line)) => line?,
+		None => return Err(FileError::Empty),
+	};
+	let words: Vec<String> = banner
+		.split_whitespace()
+		.map(|w| w.to_lowercase())
+		.collect();
+
+	if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+		return Err(FileError::Header {
+			line: 1,
+			message: String::from("expected '%%MatrixMarket matrix <format> <field> <symmetry>'"),
+		});
+	}
+
+	let coordinate = match words[2].as_str() {
+		"coordinate" => true,
+		"array" => false,
+		other => return Err(FileError::Unsupported(format!("format '{}'", other))),
+	};
+	let pattern = match words[3].as_str() {
+		"real" | "double" | "integer" => false,
+		"pattern" if coordinate => true,
+		other => return Err(FileError::Unsupported(format!("field '{}'", other))),
+	};
+	let integer = words[3] == "integer";
+	let symmetry = match words[4].as_str() {
+		"general" => Symmetry::General,
+		"symmetric" => Symmetry::Symmetric,
+		"skew-symmetric" => Symmetry::SkewSymmetric,
+		other => return Err(FileError::Unsupported(format!("symmetry '{}'", other))),
+	};
+
+	// Skip comments, then the size line
+
+	let mut data = lines.filter_map(|(i, line)| match line {
+		Ok(line) if line.trim().is_empty() || line.starts_with('%') => None,
+		Ok(line) => Some(Ok((i + 1, line))),
+		Err(error) => Some(Err(error)),
+	});
+
+	let (size_line, size) = match data.next() {
+		Some(line) => line?,
+		None => return Err(FileError::Empty),
+	};
+	let size: Vec<usize> = size
+		.split_whitespace()
+		.map(|w| w.parse::<usize>())
+		.collect::<Result<_, _>>()
+		.map_err(|_| FileError::Header {
+			line: size_line,
+			message: String::from("invalid size line"),
+		})?;
+
+	if size.len() != if coordinate { 3 } else { 2 } {
+		return Err(FileError::Header {
+			line: size_line,
+			message: String::from("invalid size line"),
+		});
+	}
+
+	let (rows, cols) = (size[0], size[1]);
+
+	if symmetry != Symmetry::General && rows != cols {
+		return Err(FileError::Header {
+			line: size_line,
+			message: String::from("symmetric matrix must be square"),
+		});
+	}
+
+	let parse_value = |value: &str, line: usize| -> Result<K, FileError> {
+		if integer && value.parse::<i64>().is_err() {
+			return Err(FileError::InvalidNumber {
+				line,
+				value: value.to_string(),
+			});
+		}
+		return number(value, line);
+	};
+
+	// Array files are column-major over the stored triangle
+
+	let first = |j: usize| match symmetry {
+		Symmetry::General => 0,
+		Symmetry::Symmetric => j,
+		Symmetry::SkewSymmetric => j + 1,
+	};
+	let expected = if coordinate {
+		size[2]
+	} else {
+		match rows.checked_mul(cols).filter(|&size| size <= MAX_ARRAY) {
+			Some(size) => match symmetry {
+				Symmetry::General => size,
+				Symmetry::Symmetric => (size + rows) / 2,
+				Symmetry::SkewSymmetric => (size - rows) / 2,
+			},
+			None => return Err(FileError::TooLarge { rows, cols }),
+		}
+	};
+	let mut position = (first(0), 0);
+
+	let mut result = CooMatrix::new(rows, cols);
+	let mut found = 0;
+
+	for line in data {
+		let (line, text) = line?;
+		let fields: Vec<&str> = text.split_whitespace().collect();
+
+		if found == expected {
+			return Err(FileError::EntryCount {
+				expected,
+				found: found + 1,
+			});
+		}
+
+		let (i, j, value) = if coordinate {
+			let width = if pattern { 2 } else { 3 };
+
+			if fields.len() != width {
+				return Err(FileError::RaggedRow {
+					line,
+					expected: width,
+					found: fields.len(),
+				});
+			}
+
+			let index = |field: &str| {
+				field
+					.parse::<usize>()
+					.map_err(|_| FileError::InvalidNumber {
+						line,
+						value: field.to_string(),
+					})
+			};
+			let (i, j) = (index(fields[0])?, index(fields[1])?);
+
+			if i == 0 || j == 0 || i > rows || j > cols {
+				return Err(FileError::OutOfBounds {
+					line,
+					row: i,
+					col: j,
+				});
+			}
+
+			let value = if pattern {
+				K::from(1.)
+			} else {
+				parse_value(fields[2], line)?
+			};
+
+			(i - 1, j - 1, value)
+		} else {
+			if fields.len() != 1 {
+				return Err(FileError::RaggedRow {
+					line,
+					expected: 1,
+					found: fields.len(),
+				});
+			}
+
+			let (i, j) = position;
+
+			position = if i + 1 < rows {
+				(i + 1, j)
+			} else {
+				(first(j + 1), j + 1)
+			};
+
+			(i, j, parse_value(fields[0], line)?)
+		};
+
+		found += 1;
+		if value == K::default() {
+			continue;
+		}
+		result.push(i, j, value);
+		if i != j {
+			match symmetry {
+				Symmetry::General => {}
+				Symmetry::Symmetric => result.push(j, i, value),
+				Symmetry::SkewSymmetric => result.push(j, i, -value),
+			}
+		}
+	}
+
+	if found != expected {
+		return Err(FileError::EntryCount { expected, found });
+	}
+
+	return Ok(result);
+}
+
+pub fn write_matrix_market<K: Traits, W: Write>(
+	writer: &mut W,
+	matrix: &CooMatrix<K>,
+) -> Result<(), FileError> {
+	writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+	writeln!(writer, "{} {} {}", matrix.rows, matrix.cols, matrix.nnz())?;
+	for &(i, j, value) in &matrix.entries {
+		writeln!(writer, "{} {} {}", i + 1, j + 1, value.into())?;
+	}
+
+	return Ok(());
+}
+
+pub fn write_matrix_market_array<K, R, W>(writer: &mut W, rows: &[R]) -> Result<(), FileError>
+where
+	K: Traits,
+	R: AsRef<[K]>,
+	W: Write,
+{
+	let cols = rows.first().map_or(0, |row| row.as_ref().len());
+
+	writeln!(writer, "%%MatrixMarket matrix array real general")?;
+	writeln!(writer, "{} {}", rows.len(), cols)?;
+	for j in 0..cols {
+		for row in rows {
+			writeln!(writer, "{}", row.as_ref()[j].into())?;
+		}
+	}
+
+	return Ok(());
+}
+
+impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
+	pub fn read_csv<R: BufRead>(reader: R, options: &CsvOptions) -> Result<Self, FileError> {
+		return dense(&read_csv(reader, options)?);
+	}
+
+	pub fn write_csv<W: Write>(
+		&self,
+		writer: &mut W,
+		options: &CsvOptions,
+	) -> Result<(), FileError> {
+		return write_csv(writer, &self.data, options);
+	}
+
+	pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, FileError> {
+		let coo: CooMatrix<K> = read_matrix_market(reader)?;
+
+		if (coo.rows, coo.cols) != (M, N) {
+			return Err(FileError::Dimensions {
+				expected: (M, N),
+				found: (coo.rows, coo.cols),
+			});
+		}
+
+		return Ok(coo.to_csr().to_dense());
+	}
+
+	pub fn write_matrix_market<W: Write>(&self, writer: &mut W) -> Result<(), FileError> {
+		return write_matrix_market_array(writer, &self.data);
+	}
+}
+
+impl<K: Traits> CooMatrix<K> {
+	pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, FileError> {
+		return read_matrix_market(reader);
+	}
+
+	pub fn write_matrix_market<W: Write>(&self, writer: &mut W) -> Result<(), FileError> {
+		return write_matrix_market(writer, self);
+	}
+}
+
+impl<K: Traits> CsrMatrix<K> {
+	pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, FileError> {
+		return Ok(read_matrix_market(reader)?.to_csr());
+	}
+
+	pub fn write_matrix_market<W: Write>(&self, writer: &mut W) -> Result<(), FileError> {
+		return write_matrix_market(writer, &self.to_coo());
+	}
+}
+
+impl<K: Traits> CscMatrix<K> {
+	pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, FileError> {
+		return Ok(read_matrix_market(reader)?.to_csc());
+	}
+
+	pub fn write_matrix_market<W: Write>(&self, writer: &mut W) -> Result<(), FileError> {
+		return write_matrix_market(writer, &self.to_coo());
+	}
+}
@@ -0,0 +1,315 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   format.rs                                          :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:14:21 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:14:21 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::fmt::{self, Write};
+use std::io;
+
+use crate::matrix::Matrix;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+const MAX_DENOMINATOR: i64 = 10000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+	Bmatrix,
+	Pmatrix,
+	Markdown,
+	MathMl,
+	Unicode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormatOptions {
+	pub style: Style,
+	pub precision: Option<usize>,
+	pub rational: bool,
+}
+
+impl FormatOptions {
+	pub fn new(style: Style) -> Self {
+		Self {
+			style,
+			..Self::default()
+		}
+	}
+}
+
+impl Default for FormatOptions {
+	fn default() -> Self {
+		Self {
+			style: Style::Unicode,
+			precision: None,
+			rational: false,
+		}
+	}
+}
+
+enum Number {
+	Decimal(String),
+	Fraction(bool, i64, i64),
+}
+
+// Best fraction p/q with q <= MAX_DENOMINATOR from the continued fraction
+// expansion, kept only if it reproduces x to f32 precision
+
+fn fraction(x: f32) -> Option<(i64, i64)> {
+	if !x.is_finite() {
+		return None;
+	}
+
+	let target = x.abs() as f64;
+	let (mut p0, mut q0, mut p1, mut q1) = (0i64, 1i64, 1i64, 0i64);
+	let mut rest = target;
+
+	loop {
+		let a = rest.floor();
+
+		if a > i64::MAX as f64 / 2. {
+			return None;
+		}
+
+		let (p2, q2) = (a as i64 * p1 + p0, a as i64 * q1 + q0);
+
+		if q2 > MAX_DENOMINATOR {
+			return None;
+		}
+		(p0, q0, p1, q1) = (p1, q1, p2, q2);
+
+		if (p1 as f64 / q1 as f64 - target).abs() <= target.max(1.) * f32::EPSILON as f64 {
+			return Some((p1, q1));
+		}
+
+		rest = 1. / (rest - a);
+		if !rest.is_finite() {
+			return None;
+		}
+	}
+}
+
+fn number<K: Traits>(x: K, options: &FormatOptions) -> Number {
+	let x: f32 = x.into();
+
+	if options.rational {
+		if let Some((p, q)) = fraction(x) {
+			return Number::Fraction(x < 0. && p != 0, p, q);
+		}
+	}
+
+	return Number::Decimal(match options.precision {
+		Some(precision) => format!("{:.*}", precision, x),
+		None => format!("{}", x),
+	});
+}
+
+fn plain(number: &Number) -> String {
+	match number {
+		Number::Decimal(s) => s.clone(),
+		Number::Fraction(negative, p, 1) => format!("{}{}", if *negative { "-" } else { "" }, p),
+		Number::Fraction(negative, p, q) => {
+			format!("{}{}/{}", if *negative { "-" } else { "" }, p, q)
+		}
+	}
+}
+
+fn latex(number: &Number) -> String {
+	match number {
+		Number::Fraction(negative, p, q) if *q != 1 => {
+			format!(
+				"{}\\frac{{{}}}{{{}}}",
+				if *negative { "-" } else { "" },
+				p,
+				q
+			)
+		}
+		_ => plain(number),
+	}
+}
+
+fn mathml(number: &Number) -> String {
+	let (negative, body) = match number {
+		Number::Fraction(negative, p, q) if *q != 1 => (
+			*negative,
+			format!("<mfrac><mn>{}</mn><mn>{}</mn></mfrac>", p, q),
+		),
+		_ => {
+			let text = plain(number);
+
+			match text.strip_prefix('-') {
+				Some(rest) => (true, format!("<mn>{}</mn>", rest)),
+				None => (false, format!("<mn>{}</mn>", text)),
+			}
+		}
+	};
+
+	if negative {
+		return format!("<mrow><mo>-</mo>{}</mrow>", body);
+	}
+	return body;
+}
+
+pub fn write_rows<W, K, R>(out: &mut W, rows: &[R], options: &FormatOptions) -> fmt::Result
+where
+	W: Write,
+	K: Traits,
+	R: AsRef<[K]>,
+{
+	let cells: Vec<Vec<Number>> = rows
+		.iter()
+		.map(|row| row.as_ref().iter().map(|&x| number(x, options)).collect())
+		.collect();
+	let cols = cells.first().map_or(0, |row| row.len());
+
+	match options.style {
+		Style::Bmatrix | Style::Pmatrix => {
+			let env = if options.style == Style::Bmatrix {
+				"bmatrix"
+			} else {
+				"pmatrix"
+			};
+
+			writeln!(out, "\\begin{{{}}}", env)?;
+			for (i, row) in cells.iter().enumerate() {
+				let line: Vec<String> = row.iter().map(latex).collect();
+
+				write!(out, "{}", line.join(" & "))?;
+				writeln!(out, "{}", if i + 1 < cells.len() { " \\\\" } else { "" })?;
+			}
+			writeln!(out, "\\end{{{}}}", env)?;
+		}
+		Style::Markdown => {
+			let text: Vec<Vec<String>> = cells
+				.iter()
+				.map(|row| row.iter().map(plain).collect())
+				.collect();
+
+			writeln!(out, "|{}", "   |".repeat(cols))?;
+			writeln!(out, "|{}", "--:|".repeat(cols))?;
+			for row in &text {
+				writeln!(out, "| {} |", row.join(" | "))?;
+			}
+		}
+		Style::MathMl => {
+			write!(out, "<math><mrow><mo>[</mo><mtable>")?;
+			for row in &cells {
+				write!(out, "<mtr>")?;
+				for cell in row {
+					write!(out, "<mtd>{}</mtd>", mathml(cell))?;
+				}
+				write!(out, "</mtr>")?;
+			}
+			writeln!(out, "</mtable><mo>]</mo></mrow></math>")?;
+		}
+		Style::Unicode => {
+			let text: Vec<Vec<String>> = cells
+				.iter()
+				.map(|row| row.iter().map(plain).collect())
+				.collect();
+			let widths: Vec<usize> = (0..cols)
+				.map(|j| {
+					text.iter()
+						.map(|row| row[j].chars().count())
+						.max()
+						.unwrap_or(0)
+				})
+				.collect();
+			let inner = widths.iter().map(|w| w + 1).sum::<usize>() + 1;
+
+			writeln!(out, "┌{}┐", " ".repeat(inner))?;
+			for row in &text {
+				write!(out, "│")?;
+				for j in 0..cols {
+					write!(out, " {:>width$}", row[j], width = widths[j])?;
+				}
+				writeln!(out, " │")?;
+			}
+			writeln!(out, "└{}┘", " ".repeat(inner))?;
+		}
+	}
+
+	return Ok(());
+}
+
+fn render<K, R>(rows: &[R], options: &FormatOptions) -> String
+where
+	K: Traits,
+	R: AsRef<[K]>,
+{
+	let mut result = String::new();
+
+	// Writing into a String never fails
+	write_rows(&mut result, rows, options).unwrap();
+
+	return result;
+}
+
+impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
+	pub fn format_with(&self, options: &FormatOptions) -> String {
+		return render(&self.data, options);
+	}
+
+	pub fn write_formatted<W: Write>(&self, out: &mut W, options: &FormatOptions) -> fmt::Result {
+		return write_rows(out, &self.data, options);
+	}
+
+	pub fn write_formatted_io<W: io::Write>(
+		&self,
+		out: &mut W,
+		options: &FormatOptions,
+	) -> io::Result<()> {
+		return out.write_all(self.format_with(options).as_bytes());
+	}
+}
+
+// Vectors are rendered as columns
+
+impl<K: Traits, const N: usize> Vector<K, N> {
+	pub fn format_with(&self, options: &FormatOptions) -> String {
+		return render(&self.data.map(|x| [x]), options);
+	}
+
+	pub fn write_formatted<W: Write>(&self, out: &mut W, options: &FormatOptions) -> fmt::Result {
+		return write_rows(out, &self.data.map(|x| [x]), options);
+	}
+
+	pub fn write_formatted_io<W: io::Write>(
+		&self,
+		out: &mut W,
+		options: &FormatOptions,
+	) -> io::Result<()> {
+		return out.write_all(self.format_with(options).as_bytes());
+	}
+}
+
+// `{}` draws the Unicode box, `{:.3}` fixes the precision
+
+impl<K: Traits, const M: usize, const N: usize> fmt::Display for Matrix<K, M, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let options = FormatOptions {
+			precision: f.precision(),
+			..FormatOptions::default()
+		};
+
+		return write_rows(f, &self.data, &options);
+	}
+}
+
+impl<K: Traits, const N: usize> fmt::Display for Vector<K, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let options = FormatOptions {
+			precision: f.precision(),
+			..FormatOptions::default()
+		};
+
+		return write_rows(f, &self.data.map(|x| [x]), &options);
+	}
+}
@@ -0,0 +1,98 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   gemm.rs                                            :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:58:07 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:41:24 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use crate::traits::MulAdd;
+
+// Tile sizes: a packed KC x NC panel of B stays in L2 while MC rows of A
+// stream over it
+
+const MC: usize = 64;
+const KC: usize = 128;
+const NC: usize = 256;
+
+fn check_sizes<K>(a: &[K], b: &[K], c: &[K], m: usize, n: usize, p: usize) {
+	if a.len() != m * n || b.len() != n * p || c.len() != m * p {
+		panic!("Error: Matrix sizes are incompatible");
+	}
+}
+
+// Reference i-j-k product, every element accumulates with one FMA per k
+
+pub fn gemm_naive<K: Copy + Default + MulAdd<Output = K>>(
+	a: &[K],
+	b: &[K],
+	c: &mut [K],
+	m: usize,
+	n: usize,
+	p: usize,
+) {
+	check_sizes(a, b, c, m, n, p);
+
+	for i in 0..m {
+		for j in 0..p {
+			let mut sum = K::default();
+
+			for k in 0..n {
+				sum = a[i * n + k].mul_add(b[k * p + j], sum);
+			}
+			c[i * p + j] = sum;
+		}
+	}
+}
+
+// Blocked i-k-j product. The k blocks are visited in ascending order for
+// every element of C, so each element sees exactly the same sequence of
+// FMAs as in gemm_naive and the result is bit-identical
+
+pub fn gemm_blocked<K: Copy + Default + MulAdd<Output = K>>(
+	a: &[K],
+	b: &[K],
+	c: &mut [K],
+	m: usize,
+	n: usize,
+	p: usize,
+) {
+	check_sizes(a, b, c, m, n, p);
+
+	c.fill(K::default());
+
+	let mut packed = vec![K::default(); KC.min(n) * NC.min(p)];
+
+	for jj in (0..p).step_by(NC) {
+		let nc = NC.min(p - jj);
+
+		for kk in (0..n).step_by(KC) {
+			let kc = KC.min(n - kk);
+
+			// Pack the B panel so the inner loop reads contiguous memory
+
+			for k in 0..kc {
+				let row = (kk + k) * p + jj;
+				packed[k * nc..(k + 1) * nc].copy_from_slice(&b[row..row + nc]);
+			}
+
+			for ii in (0..m).step_by(MC) {
+				let mc = MC.min(m - ii);
+
+				for i in ii..ii + mc {
+					let c_row = &mut c[i * p + jj..i * p + jj + nc];
+
+					for k in 0..kc {
+						let a_ik = a[i * n + kk + k];
+
+						K::axpy(a_ik, &packed[k * nc..(k + 1) * nc], c_row);
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,110 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   interpolation.rs                                   :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:54:59 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 02:54:59 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::ops::{Add, Mul};
+
+pub fn lerp<V>(u: V, v: V, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+{
+	u * (1. - t) + v * t
+}
+
+pub fn lerp_clamped<V>(u: V, v: V, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+{
+	lerp(u, v, t.clamp(0., 1.))
+}
+
+pub fn inverse_lerp(a: f32, b: f32, value: f32) -> f32 {
+	if a == b {
+		return f32::NAN;
+	}
+
+	return (value - a) / (b - a);
+}
+
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+	let t = inverse_lerp(edge0, edge1, x).clamp(0., 1.);
+
+	return t * t * (3. - 2. * t);
+}
+
+pub fn lerp_smooth<V>(u: V, v: V, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+{
+	lerp(u, v, smoothstep(0., 1., t))
+}
+
+pub fn hermite<V>(p0: V, m0: V, p1: V, m1: V, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+{
+	let t2 = t * t;
+	let t3 = t2 * t;
+
+	let h00 = 2. * t3 - 3. * t2 + 1.;
+	let h10 = t3 - 2. * t2 + t;
+	let h01 = -2. * t3 + 3. * t2;
+	let h11 = t3 - t2;
+
+	p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
+
+pub fn catmull_rom<V>(p0: V, p1: V, p2: V, p3: V, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+{
+	let t2 = t * t;
+	let t3 = t2 * t;
+
+	// Uniform Catmull-Rom spline expanded into one weight per control point
+
+	let w0 = 0.5 * (-t3 + 2. * t2 - t);
+	let w1 = 0.5 * (3. * t3 - 5. * t2 + 2.);
+	let w2 = 0.5 * (-3. * t3 + 4. * t2 + t);
+	let w3 = 0.5 * (t3 - t2);
+
+	p0 * w0 + p1 * w1 + p2 * w2 + p3 * w3
+}
+
+pub fn bezier<V, I>(points: I, t: f32) -> V
+where
+	V: Mul<f32, Output = V> + Add<Output = V>,
+	I: IntoIterator<Item = V>,
+	I::IntoIter: ExactSizeIterator,
+{
+	let mut points = points.into_iter();
+
+	if points.len() == 0 {
+		panic!("Error: Bezier curve needs at least one control point");
+	}
+
+	// Bernstein form, so each control point is consumed exactly once
+
+	let degree = points.len() - 1;
+	let mut binomial: f32 = 1.;
+
+	let mut result = points.next().unwrap() * (1. - t).powi(degree as i32);
+
+	for (i, point) in points.enumerate() {
+		let i = i + 1;
+		binomial = binomial * (degree - i + 1) as f32 / i as f32;
+
+		let weight = binomial * t.powi(i as i32) * (1. - t).powi((degree - i) as i32);
+		result = result + point * weight;
+	}
+
+	return result;
+}
@@ -0,0 +1,39 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   lib.rs                                             :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:58:06 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:49:50 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+pub mod binary;
+pub mod blas;
+pub mod calculator;
+pub mod checksum;
+pub mod cli;
+pub mod elimination;
+pub mod file;
+pub mod format;
+pub mod gemm;
+pub mod interpolation;
+pub mod matrix;
+pub mod npy;
+pub mod parallel;
+pub mod parse;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod simd;
+pub mod solver;
+pub mod sparse;
+pub mod traits;
+pub mod vector;
+pub mod view;
+
+#[cfg(test)]
+mod test;
+
+pub use interpolation::lerp;
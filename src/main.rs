@@ -6,28 +6,78 @@
 /*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
 /*                                                +#+#+#+#+#+   +#+           */
 /*   Created: 2024/12/24 13:13:04 by ggalon            #+#    #+#             */
-/*   Updated: 2026/01/25 15:47:01 by ggalon           ###   ########.fr       */
+/*   Updated: 2026/10/19 03:49:50 by ggalon           ###   ########.fr       */
 /*                                                                            */
 /* ************************************************************************** */
 
-mod matrix;
-mod traits;
-mod vector;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
 
-#[cfg(test)]
-mod test;
+use matrix::calculator::Calculator;
+use matrix::cli;
 
-use std::ops::{Add, Mul};
+const HELP: &str = "\
+Values:     2.5   [1 2 3]   [1 2; 3 4]   [[1, 2], [3, 4]]
+Variables:  a = [1 2; 3 4]   (the last result is kept in ans)
+Operators:  + - * and / by a scalar, parentheses
+Functions:  det inv rank rref trace transpose dot cross solve(a, b) lerp(a, b, t)
+Commands:   help vars history quit";
 
-pub fn lerp<V>(u: V, v: V, t: f32) -> V
-where
-	V: Mul<f32, Output = V> + Add<Output = V>,
-{
-	if t < 0. || t > 1. {
-		panic!("Scalar need to be between 0 and 1")
+fn repl() {
+	let mut calculator = Calculator::new();
+	let stdin = io::stdin();
+	let interactive = stdin.is_terminal();
+
+	if interactive {
+		println!("Matrix calculator, type help for a summary");
 	}
 
-	u * (1. - t) + v * t
+	loop {
+		if interactive {
+			print!("> ");
+			io::stdout().flush().ok();
+		}
+
+		let mut line = String::new();
+
+		match stdin.lock().read_line(&mut line) {
+			Ok(0) => break,
+			Ok(_) => {}
+			Err(error) => {
+				eprintln!("Error: {}", error);
+				break;
+			}
+		}
+
+		match line.trim() {
+			"quit" | "exit" => break,
+			"help" => println!("{}", HELP),
+			"history" => {
+				for (i, entry) in calculator.history().iter().enumerate() {
+					println!("{:>4}  {}", i + 1, entry);
+				}
+			}
+			"vars" => {
+				for (name, value) in calculator.variables() {
+					println!("{} = {}", name, value.kind());
+				}
+			}
+			line => match calculator.eval(line) {
+				Ok(Some(value)) => println!("{}", value.to_string().trim_end()),
+				Ok(None) => {}
+				Err(error) => eprintln!("Error: {}", error),
+			},
+		}
+	}
 }
 
-fn main() {}
+fn main() -> ExitCode {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+
+	if args.is_empty() || args[0] == "repl" {
+		repl();
+		return ExitCode::SUCCESS;
+	}
+
+	return cli::run(&args);
+}
@@ -6,16 +6,20 @@
 /*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
 /*                                                +#+#+#+#+#+   +#+           */
 /*   Created: 2024/12/24 14:20:04 by ggalon            #+#    #+#             */
-/*   Updated: 2026/01/25 15:47:03 by ggalon           ###   ########.fr       */
+/*   Updated: 2026/10/19 04:11:57 by ggalon           ###   ########.fr       */
 /*                                                                            */
 /* ************************************************************************** */
 
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use crate::elimination::{self, EliminationRecord, Form, Observer};
+use crate::parallel;
 use crate::traits::Traits;
 use crate::vector::Vector;
+use crate::view::{BlockView, BlockViewMut, ColumnView, ColumnViewMut};
 
 #[allow(dead_code)]
+#[derive(Debug, PartialEq)]
 pub struct Matrix<K, const M: usize, const N: usize> {
 	pub data: [[K; N]; M],
 	pub size_x: usize,
@@ -37,8 +41,85 @@ impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
 		}
 	}
 
-	pub fn clone(&self) -> Self {
-		return Self::new(self.data);
+	pub fn zeros() -> Self {
+		return Self::new([[K::default(); N]; M]);
+	}
+
+	pub fn ones() -> Self {
+		return Self::new([[K::from(1.); N]; M]);
+	}
+
+	pub fn identity() -> Self {
+		let mut result = Self::zeros();
+
+		for i in 0..M.min(N) {
+			result.data[i][i] = K::from(1.);
+		}
+
+		return result;
+	}
+
+	pub fn from_fn<F: FnMut(usize, usize) -> K>(mut f: F) -> Self {
+		let mut result = Self::zeros();
+
+		for i in 0..M {
+			for j in 0..N {
+				result.data[i][j] = f(i, j);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn from_rows(rows: &[Vector<K, N>; M]) -> Self {
+		return Self::from_fn(|i, j| rows[i].data[j]);
+	}
+
+	pub fn from_columns(columns: &[Vector<K, M>; N]) -> Self {
+		return Self::from_fn(|i, j| columns[j].data[i]);
+	}
+
+	pub fn vandermonde(points: &Vector<K, M>) -> Self {
+		let mut result = Self::zeros();
+
+		for i in 0..M {
+			let mut power = K::from(1.);
+
+			for j in 0..N {
+				result.data[i][j] = power;
+				power *= points.data[i];
+			}
+		}
+
+		return result;
+	}
+
+	pub fn hilbert() -> Self {
+		return Self::from_fn(|i, j| K::from(1. / (i + j + 1) as f32));
+	}
+
+	// Constant along diagonals, the first column wins on the main diagonal
+
+	pub fn toeplitz(column: &Vector<K, M>, row: &Vector<K, N>) -> Self {
+		return Self::from_fn(|i, j| {
+			if i >= j {
+				column.data[i - j]
+			} else {
+				row.data[j - i]
+			}
+		});
+	}
+
+	#[cfg(feature = "rand")]
+	pub fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+		return Self::from_fn(|_, _| K::from(rng.random::<f32>()));
+	}
+
+	#[cfg(feature = "rand")]
+	pub fn random_seeded(seed: u64) -> Self {
+		use rand::SeedableRng;
+
+		return Self::random(&mut rand::rngs::StdRng::seed_from_u64(seed));
 	}
 
 	#[allow(dead_code)]
@@ -73,21 +154,22 @@ impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
 	}
 
 	pub fn mul_mat<const P: usize>(&self, mat: Matrix<K, N, P>) -> Matrix<K, M, P> {
-		let mut result: Matrix<K, M, P> = Matrix::new([[K::default(); P]; M]);
+		let mut result: Matrix<K, M, P> = Matrix::zeros();
 
-		for i in 0..M {
-			for j in 0..P {
-				for k in 0..N {
-					result.data[i][j] = self.data[i][k].mul_add(mat.data[k][j], result.data[i][j]);
-				}
-			}
-		}
+		parallel::gemm(
+			self.data.as_flattened(),
+			mat.data.as_flattened(),
+			result.data.as_flattened_mut(),
+			M,
+			N,
+			P,
+		);
 
 		return result;
 	}
 
 	pub fn mul_vec(&self, vec: Vector<K, N>) -> Matrix<K, M, 1> {
-		let mut result: Matrix<K, M, 1> = Matrix::new([[K::default(); 1]; M]);
+		let mut result: Matrix<K, M, 1> = Matrix::zeros();
 
 		for i in 0..M {
 			for k in 0..N {
@@ -98,7 +180,7 @@ impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
 	}
 
 	pub fn transpose(&self) -> Matrix<K, N, M> {
-		let mut result: Matrix<K, N, M> = Matrix::new([[K::default(); M]; N]);
+		let mut result: Matrix<K, N, M> = Matrix::zeros();
 
 		for i in 0..M {
 			for j in 0..N {
@@ -112,75 +194,431 @@ impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
 	pub fn row_echelon(&self) -> Matrix<K, M, N> {
 		let mut result: Matrix<K, M, N> = self.clone();
 
-		let mut pivot_row = 0;
+		elimination::eliminate(&mut result.data, N, Form::Echelon, 0., None);
 
-		for col in 0..N {
-			if pivot_row >= M {
-				break;
-			}
+		return result;
+	}
 
-			// Get the max row and swap
+	pub fn reduced_row_echelon(&self) -> Matrix<K, M, N> {
+		let mut result: Matrix<K, M, N> = self.clone();
 
-			let mut max_row = pivot_row;
-			for i in pivot_row + 1..M {
-				if result.data[i][col].into().abs() > result.data[max_row][col].into().abs() {
-					max_row = i;
-				}
+		elimination::eliminate(&mut result.data, N, Form::Reduced, 0., None);
+
+		return result;
+	}
+
+	pub fn row_echelon_with_record(&self) -> (Matrix<K, M, N>, EliminationRecord<K>) {
+		let mut result: Matrix<K, M, N> = self.clone();
+		let mut record = EliminationRecord::new();
+
+		elimination::eliminate(&mut result.data, N, Form::Echelon, 0., Some(&mut record));
+
+		return (result, record);
+	}
+
+	pub fn reduced_row_echelon_with_record(&self) -> (Matrix<K, M, N>, EliminationRecord<K>) {
+		let mut result: Matrix<K, M, N> = self.clone();
+		let mut record = EliminationRecord::new();
+
+		elimination::eliminate(&mut result.data, N, Form::Reduced, 0., Some(&mut record));
+
+		return (result, record);
+	}
+
+	pub fn row_echelon_traced(&self, observer: &mut dyn Observer<K>) -> Matrix<K, M, N> {
+		let mut result: Matrix<K, M, N> = self.clone();
+
+		elimination::eliminate(&mut result.data, N, Form::Echelon, 0., Some(observer));
+
+		return result;
+	}
+
+	pub fn reduced_row_echelon_traced(&self, observer: &mut dyn Observer<K>) -> Matrix<K, M, N> {
+		let mut result: Matrix<K, M, N> = self.clone();
+
+		elimination::eliminate(&mut result.data, N, Form::Reduced, 0., Some(observer));
+
+		return result;
+	}
+
+	pub fn row(&self, i: usize) -> &[K; N] {
+		return &self.data[i];
+	}
+
+	pub fn row_mut(&mut self, i: usize) -> &mut [K; N] {
+		return &mut self.data[i];
+	}
+
+	pub fn column(&self, j: usize) -> ColumnView<'_, K, N> {
+		return ColumnView::new(&self.data, j);
+	}
+
+	pub fn column_mut(&mut self, j: usize) -> ColumnViewMut<'_, K, N> {
+		return ColumnViewMut::new(&mut self.data, j);
+	}
+
+	pub fn iter_rows(&self) -> std::slice::Iter<'_, [K; N]> {
+		return self.data.iter();
+	}
+
+	pub fn iter_columns(&self) -> impl Iterator<Item = ColumnView<'_, K, N>> + '_ {
+		return (0..N).map(|j| self.column(j));
+	}
+
+	pub fn block(&self, row: usize, col: usize, rows: usize, cols: usize) -> BlockView<'_, K, N> {
+		return BlockView::new(&self.data, row, col, rows, cols);
+	}
+
+	pub fn block_mut(
+		&mut self,
+		row: usize,
+		col: usize,
+		rows: usize,
+		cols: usize,
+	) -> BlockViewMut<'_, K, N> {
+		return BlockViewMut::new(&mut self.data, row, col, rows, cols);
+	}
+
+	pub fn submatrix<const R: usize, const C: usize>(
+		&self,
+		row: usize,
+		col: usize,
+	) -> Matrix<K, R, C> {
+		return self.block(row, col, R, C).to_matrix();
+	}
+
+	pub fn set_block<const R: usize, const C: usize>(
+		&mut self,
+		row: usize,
+		col: usize,
+		block: &Matrix<K, R, C>,
+	) {
+		self.block_mut(row, col, R, C).assign(block);
+	}
+
+	pub fn minor<const R: usize, const C: usize>(&self, row: usize, col: usize) -> Matrix<K, R, C> {
+		const {
+			assert!(
+				R + 1 == M && C + 1 == N,
+				"Error: minor result needs (M - 1) x (N - 1)"
+			)
+		};
+
+		if row >= M || col >= N {
+			panic!("Error: Index is out of the matrix bounds");
+		}
+
+		let mut result: Matrix<K, R, C> = Matrix::zeros();
+
+		for (i, source) in self.data.iter().enumerate().filter(|&(i, _)| i != row) {
+			let target = if i < row { i } else { i - 1 };
+
+			result.data[target][..col].copy_from_slice(&source[..col]);
+			result.data[target][col..].copy_from_slice(&source[col + 1..]);
+		}
+
+		return result;
+	}
+
+	// Stacking: the result sizes are const parameters checked when the
+	// function is instantiated, so a wrong size fails to compile
+
+	pub fn hstack<const P: usize, const Q: usize>(
+		&self,
+		other: &Matrix<K, M, P>,
+	) -> Matrix<K, M, Q> {
+		const { assert!(Q == N + P, "Error: hstack result needs N + P columns") };
+
+		let mut result: Matrix<K, M, Q> = Matrix::zeros();
+
+		result.set_block(0, 0, self);
+		result.set_block(0, N, other);
+
+		return result;
+	}
+
+	pub fn vstack<const P: usize, const R: usize>(
+		&self,
+		other: &Matrix<K, P, N>,
+	) -> Matrix<K, R, N> {
+		const { assert!(R == M + P, "Error: vstack result needs M + P rows") };
+
+		let mut result: Matrix<K, R, N> = Matrix::zeros();
+
+		result.set_block(0, 0, self);
+		result.set_block(M, 0, other);
+
+		return result;
+	}
+
+	pub fn block_diag<const P: usize, const Q: usize, const R: usize, const S: usize>(
+		&self,
+		other: &Matrix<K, P, Q>,
+	) -> Matrix<K, R, S> {
+		const {
+			assert!(
+				R == M + P && S == N + Q,
+				"Error: block_diag result needs (M + P) x (N + Q)"
+			)
+		};
+
+		let mut result: Matrix<K, R, S> = Matrix::zeros();
+
+		result.set_block(0, 0, self);
+		result.set_block(M, N, other);
+
+		return result;
+	}
+
+	pub fn from_blocks<const M1: usize, const N1: usize, const M2: usize, const N2: usize>(
+		top_left: &Matrix<K, M1, N1>,
+		top_right: &Matrix<K, M1, N2>,
+		bottom_left: &Matrix<K, M2, N1>,
+		bottom_right: &Matrix<K, M2, N2>,
+	) -> Matrix<K, M, N> {
+		const {
+			assert!(
+				M == M1 + M2 && N == N1 + N2,
+				"Error: Blocks don't add up to the matrix size"
+			)
+		};
+
+		let mut result: Matrix<K, M, N> = Matrix::zeros();
+
+		result.set_block(0, 0, top_left);
+		result.set_block(0, N1, top_right);
+		result.set_block(M1, 0, bottom_left);
+		result.set_block(M1, N1, bottom_right);
+
+		return result;
+	}
+
+	pub fn kronecker<const P: usize, const Q: usize, const R: usize, const S: usize>(
+		&self,
+		other: &Matrix<K, P, Q>,
+	) -> Matrix<K, R, S> {
+		const {
+			assert!(
+				R == M * P && S == N * Q,
+				"Error: kronecker result needs (M * P) x (N * Q)"
+			)
+		};
+
+		let mut result: Matrix<K, R, S> = Matrix::zeros();
+
+		for i in 0..M {
+			for j in 0..N {
+				result.set_block(i * P, j * Q, &(other.clone() * self.data[i][j]));
 			}
+		}
 
-			if max_row != pivot_row {
-				result.data.swap(pivot_row, max_row);
+		return result;
+	}
+
+	pub fn hsplit<const L: usize, const R: usize>(&self) -> (Matrix<K, M, L>, Matrix<K, M, R>) {
+		const { assert!(L + R == N, "Error: hsplit parts need L + R == N columns") };
+
+		return (self.submatrix(0, 0), self.submatrix(0, L));
+	}
+
+	pub fn vsplit<const T: usize, const B: usize>(&self) -> (Matrix<K, T, N>, Matrix<K, B, N>) {
+		const { assert!(T + B == M, "Error: vsplit parts need T + B == M rows") };
+
+		return (self.submatrix(0, 0), self.submatrix(T, 0));
+	}
+
+	pub fn map<L: Traits, F: FnMut(K) -> L>(&self, mut f: F) -> Matrix<L, M, N> {
+		return Matrix::from_fn(|i, j| f(self.data[i][j]));
+	}
+
+	pub fn zip_map<L: Traits, F: FnMut(K, K) -> L>(
+		&self,
+		v: &Matrix<K, M, N>,
+		mut f: F,
+	) -> Matrix<L, M, N> {
+		return Matrix::from_fn(|i, j| f(self.data[i][j], v.data[i][j]));
+	}
+
+	pub fn apply<F: FnMut(K) -> K>(&mut self, mut f: F) {
+		for i in 0..M {
+			for j in 0..N {
+				self.data[i][j] = f(self.data[i][j]);
 			}
+		}
+	}
+
+	pub fn fold<A, F: FnMut(A, K) -> A>(&self, init: A, mut f: F) -> A {
+		let mut result = init;
 
-			if result.data[pivot_row][col] == K::default() {
-				continue;
+		for i in 0..M {
+			for j in 0..N {
+				result = f(result, self.data[i][j]);
 			}
+		}
 
-			// Normalize the pivot row
+		return result;
+	}
+
+	pub fn sum(&self) -> K {
+		return self.fold(K::default(), |acc, x| acc + x);
+	}
 
-			let pivot = result.data[pivot_row][col];
+	pub fn product(&self) -> K {
+		return self.fold(K::from(1.), |acc, x| acc * x);
+	}
 
-			for j in col..N {
-				result.data[pivot_row][j] /= pivot;
+	pub fn argmin(&self) -> Option<(usize, usize)> {
+		if M == 0 || N == 0 {
+			return None;
+		}
+
+		let mut result = (0, 0);
+
+		for i in 0..M {
+			for j in 0..N {
+				if self.data[i][j] < self.data[result.0][result.1] {
+					result = (i, j);
+				}
 			}
+		}
 
-			// Cancel the elements
+		return Some(result);
+	}
+
+	pub fn argmax(&self) -> Option<(usize, usize)> {
+		if M == 0 || N == 0 {
+			return None;
+		}
+
+		let mut result = (0, 0);
 
-			for i in 0..M {
-				if i == pivot_row {
-					continue;
+		for i in 0..M {
+			for j in 0..N {
+				if self.data[i][j] > self.data[result.0][result.1] {
+					result = (i, j);
 				}
+			}
+		}
 
-				let factor = result.data[i][col];
+		return Some(result);
+	}
+
+	pub fn min(&self) -> Option<K> {
+		return self.argmin().map(|(i, j)| self.data[i][j]);
+	}
 
-				for j in col..N {
-					result.data[i][j] =
-						(-factor).mul_add(result.data[pivot_row][j], result.data[i][j]);
+	pub fn max(&self) -> Option<K> {
+		return self.argmax().map(|(i, j)| self.data[i][j]);
+	}
+
+	pub fn any<F: FnMut(K) -> bool>(&self, f: F) -> bool {
+		return self.data.as_flattened().iter().copied().any(f);
+	}
+
+	pub fn all<F: FnMut(K) -> bool>(&self, f: F) -> bool {
+		return self.data.as_flattened().iter().copied().all(f);
+	}
+
+	pub fn is_upper_triangular(&self, tolerance: f32) -> bool {
+		for i in 0..M {
+			for j in 0..i.min(N) {
+				if self.data[i][j].into().abs() > tolerance {
+					return false;
 				}
 			}
+		}
 
-			pivot_row += 1;
+		return true;
+	}
+
+	pub fn is_lower_triangular(&self, tolerance: f32) -> bool {
+		for i in 0..M {
+			for j in i + 1..N {
+				if self.data[i][j].into().abs() > tolerance {
+					return false;
+				}
+			}
 		}
 
-		return result;
+		return true;
+	}
+
+	pub fn is_diagonal(&self, tolerance: f32) -> bool {
+		return self.is_upper_triangular(tolerance) && self.is_lower_triangular(tolerance);
 	}
 
 	pub fn rank(&self) -> usize {
-		let copy = self.row_echelon();
+		let (_, record) = self.eliminated(Form::Echelon);
+
+		return record.rank();
+	}
+
+	// Elimination for rank and the subspace queries, where pivots within
+	// rounding noise count as zero
+
+	fn eliminated(&self, form: Form) -> (Matrix<K, M, N>, EliminationRecord<K>) {
+		let mut result: Matrix<K, M, N> = self.clone();
+		let mut record = EliminationRecord::new();
+		let tolerance = elimination::tolerance(&result.data);
+
+		elimination::eliminate(&mut result.data, N, form, tolerance, Some(&mut record));
+
+		return (result, record);
+	}
+
+	pub fn pivot_columns(&self) -> Vec<usize> {
+		let (_, record) = self.eliminated(Form::Echelon);
+
+		return record.pivots.iter().map(|&(_, j)| j).collect();
+	}
 
-		let mut result: usize = usize::default();
+	pub fn row_space(&self) -> Vec<Vector<K, N>> {
+		let (echelon, _) = self.eliminated(Form::Reduced);
+		let mut result = Vec::new();
 
 		for i in 0..M {
-			for j in 0..N {
-				if copy.data[i][j] != K::default() {
-					result += 1;
+			if echelon.data[i].iter().any(|&x| x != K::default()) {
+				result.push(Vector::new(echelon.data[i]));
+			}
+		}
 
-					break;
-				}
+		return result;
+	}
+
+	pub fn column_space(&self) -> Vec<Vector<K, M>> {
+		let mut result = Vec::new();
+
+		for j in self.pivot_columns() {
+			result.push(Vector::new(std::array::from_fn(|i| self.data[i][j])));
+		}
+
+		return result;
+	}
+
+	pub fn null_space(&self) -> Vec<Vector<K, N>> {
+		let (echelon, record) = self.eliminated(Form::Reduced);
+		let pivots: Vec<usize> = record.pivots.iter().map(|&(_, j)| j).collect();
+		let mut result = Vec::new();
+
+		// One basis vector per free column: set it to 1 and solve the pivot
+		// variables from the reduced rows
+
+		for free in (0..N).filter(|j| !pivots.contains(j)) {
+			let mut vector = Vector::new([K::default(); N]);
+
+			vector.data[free] = K::from(1.);
+			for (row, &pivot) in pivots.iter().enumerate() {
+				vector.data[pivot] = -echelon.data[row][free];
 			}
+			result.push(vector);
 		}
+
 		return result;
 	}
+
+	pub fn left_null_space(&self) -> Vec<Vector<K, M>> {
+		return self.transpose().null_space();
+	}
 }
 
 impl<K: Traits + Neg<Output = K>, const N: usize> Matrix<K, N, N> {
@@ -213,18 +651,10 @@ impl<K: Traits + Neg<Output = K>, const N: usize> Matrix<K, N, N> {
 			let mut result = K::default();
 
 			for i in 0..4 {
-				let mut submatrix = Matrix::new([[K::default(); 3]; 3]);
-
-				for j in 1..4 {
-					let mut col = 0;
+				// Not minor: its size check would fail for every other N
 
-					for k in 0..4 {
-						if k != i {
-							submatrix.data[j - 1][col] = self.data[j][k];
-							col += 1;
-						}
-					}
-				}
+				let submatrix: Matrix<K, 3, 3> =
+					Matrix::from_fn(|r, c| self.data[r + 1][if c < i { c } else { c + 1 }]);
 
 				let sub_det = submatrix.determinant();
 				if i % 2 == 0 {
@@ -243,19 +673,11 @@ impl<K: Traits + Neg<Output = K>, const N: usize> Matrix<K, N, N> {
 	pub fn inverse(&self) -> Matrix<K, N, N> {
 		let mut base: Matrix<K, N, N> = self.clone();
 
-		let mut idtt: Matrix<K, N, N> = Matrix::new([[K::default(); N]; N]);
-
-		for i in 0..N {
-			idtt.data[i][i] = K::from(1.);
-		}
+		let mut idtt: Matrix<K, N, N> = Matrix::identity();
 
-		let mut pivot_row = 0;
-
-		for col in 0..N {
-			if pivot_row >= N {
-				break;
-			}
+		// A singular column panics, so every column gets the next pivot row
 
+		for (pivot_row, col) in (0..N).enumerate() {
 			// Get the max row and swap
 
 			let mut max_row: usize = pivot_row;
@@ -286,23 +708,320 @@ impl<K: Traits + Neg<Output = K>, const N: usize> Matrix<K, N, N> {
 
 			// Cancel the elements
 
+			let base_pivot = base.data[pivot_row];
+			let idtt_pivot = idtt.data[pivot_row];
+
+			parallel::for_each_row_pair(
+				&mut base.data,
+				&mut idtt.data,
+				2 * N,
+				|i, base_row, idtt_row| {
+					if i == pivot_row {
+						return;
+					}
+
+					let factor: K = base_row[col];
+
+					for j in 0..N {
+						base_row[j] = (-factor).mul_add(base_pivot[j], base_row[j]);
+						idtt_row[j] = (-factor).mul_add(idtt_pivot[j], idtt_row[j]);
+					}
+				},
+			);
+		}
+
+		return idtt;
+	}
+
+	// Gauss-Jordan on [A | I], reported to the observer step by step
+
+	pub fn inverse_traced(&self, observer: &mut dyn Observer<K>) -> Matrix<K, N, N> {
+		let mut augmented: Vec<Vec<K>> = (0..N)
+			.map(|i| {
+				let mut row = self.data[i].to_vec();
+				row.extend((0..N).map(|j| if i == j { K::from(1.) } else { K::default() }));
+				row
+			})
+			.collect();
+
+		// Exact pivots, the same rule as inverse
+
+		elimination::eliminate(&mut augmented, N, Form::Reduced, 0., Some(observer));
+
+		// Full rank leaves the identity on the left
+
+		if (0..N).any(|i| augmented[i][i] == K::default()) {
+			panic!("Matrix is singular, can't compute inverse");
+		}
+
+		return Matrix::from_fn(|i, j| augmented[i][N + j]);
+	}
+
+	pub fn from_diagonal(diagonal: &Vector<K, N>) -> Self {
+		let mut result = Self::zeros();
+
+		for i in 0..N {
+			result.data[i][i] = diagonal.data[i];
+		}
+
+		return result;
+	}
+
+	fn norm_one(&self) -> f32 {
+		let mut result: f32 = 0.;
+
+		for j in 0..N {
+			let mut sum: f32 = 0.;
+
 			for i in 0..N {
-				if i == pivot_row {
-					continue;
-				}
+				sum += self.data[i][j].into().abs();
+			}
 
-				let factor: K = base.data[i][col];
+			if sum > result || sum.is_nan() {
+				result = sum;
+			}
+		}
 
-				for j in 0..N {
-					base.data[i][j] = (-factor).mul_add(base.data[pivot_row][j], base.data[i][j]);
-					idtt.data[i][j] = (-factor).mul_add(idtt.data[pivot_row][j], idtt.data[i][j]);
-				}
+		return result;
+	}
+
+	pub fn pow(&self, n: i32) -> Matrix<K, N, N> {
+		let mut base = if n < 0 { self.inverse() } else { self.clone() };
+		let mut exponent = n.unsigned_abs();
+		let mut result = Self::identity();
+
+		// Binary exponentiation
+
+		while exponent > 0 {
+			if exponent & 1 == 1 {
+				result = result.mul_mat(base.clone());
 			}
 
-			pivot_row += 1;
+			exponent >>= 1;
+
+			if exponent > 0 {
+				base = base.mul_mat(base.clone());
+			}
 		}
 
-		return idtt;
+		return result;
+	}
+
+	pub fn powf(&self, p: f32) -> Matrix<K, N, N> {
+		return (self.log() * K::from(p)).exp();
+	}
+
+	pub fn exp(&self) -> Matrix<K, N, N> {
+		const PADE_DEGREE: usize = 6;
+
+		// Scale the matrix so that its norm is at most 0.5
+
+		let norm = self.norm_one();
+		let mut squarings: i32 = 0;
+
+		// A NaN or infinite entry would ask for endless squarings
+
+		if !norm.is_finite() {
+			return Matrix::from_fn(|_, _| K::from(f32::NAN));
+		}
+
+		if norm > 0.5 {
+			squarings = (norm / 0.5).log2().ceil() as i32;
+		}
+
+		let scaled = self.clone() * K::from(0.5f32.powi(squarings));
+
+		// Diagonal Pade approximant: exp(X) ~ D(X)^-1 * N(X)
+
+		let identity = Self::identity();
+		let mut numerator = identity.clone();
+		let mut denominator = identity.clone();
+		let mut power = identity;
+		let mut coefficient: f32 = 1.;
+
+		for k in 1..=PADE_DEGREE {
+			coefficient *= (PADE_DEGREE - k + 1) as f32 / (k * (2 * PADE_DEGREE - k + 1)) as f32;
+			power = power.mul_mat(scaled.clone());
+
+			let term = power.clone() * K::from(coefficient);
+
+			numerator += term.clone();
+			if k % 2 == 0 {
+				denominator += term;
+			} else {
+				denominator -= term;
+			}
+		}
+
+		let mut result = denominator.inverse().mul_mat(numerator);
+
+		// Undo the scaling by repeated squaring
+
+		for _ in 0..squarings {
+			result = result.mul_mat(result.clone());
+		}
+
+		return result;
+	}
+
+	pub fn sqrt(&self) -> Matrix<K, N, N> {
+		const MAX_ITERATIONS: usize = 100;
+		const TOLERANCE: f32 = 1e-6;
+
+		// Denman-Beavers iteration: Y converges to sqrt(A), Z to sqrt(A)^-1
+
+		let half = K::from(0.5);
+		let mut y = self.clone();
+		let mut z = Self::identity();
+
+		for _ in 0..MAX_ITERATIONS {
+			let next_y = (y.clone() + z.inverse()) * half;
+			let next_z = (z.clone() + y.inverse()) * half;
+
+			let delta = (next_y.clone() - y).norm_one();
+			let scale = next_y.norm_one();
+
+			y = next_y;
+			z = next_z;
+
+			if delta <= TOLERANCE * scale.max(1.) {
+				return y;
+			}
+		}
+
+		panic!("Error: Matrix square root did not converge");
+	}
+
+	pub fn log(&self) -> Matrix<K, N, N> {
+		const MAX_SQUARE_ROOTS: i32 = 64;
+		const MAX_TERMS: usize = 100;
+
+		let identity = Self::identity();
+
+		// Take square roots until the matrix is close to the identity
+
+		let mut reduced = self.clone();
+		let mut roots: i32 = 0;
+
+		while (reduced.clone() - identity.clone()).norm_one() > 0.25 {
+			if roots >= MAX_SQUARE_ROOTS {
+				panic!("Error: Matrix logarithm did not converge");
+			}
+			reduced = reduced.sqrt();
+			roots += 1;
+		}
+
+		// log(A) = 2 * atanh(Z) with Z = (A - I)(A + I)^-1
+
+		let z = (reduced.clone() - identity.clone()).mul_mat((reduced + identity).inverse());
+		let z_squared = z.clone().mul_mat(z.clone());
+		let mut power = z.clone();
+		let mut result = z;
+
+		for k in 1..MAX_TERMS {
+			power = power.mul_mat(z_squared.clone());
+
+			let term = power.clone() * K::from(1. / (2 * k + 1) as f32);
+
+			if term.norm_one() <= f32::EPSILON * result.norm_one() {
+				break;
+			}
+			result += term;
+		}
+
+		return result * K::from(2f32.powi(roots + 1));
+	}
+
+	pub fn is_symmetric(&self, tolerance: f32) -> bool {
+		let transposed = self.transpose();
+
+		return self
+			.zip_map(&transposed, |a, b| (a - b).into().abs())
+			.all(|d| d <= tolerance);
+	}
+
+	pub fn is_skew_symmetric(&self, tolerance: f32) -> bool {
+		let transposed = self.transpose();
+
+		return self
+			.zip_map(&transposed, |a, b| (a + b).into().abs())
+			.all(|d| d <= tolerance);
+	}
+
+	pub fn is_identity(&self, tolerance: f32) -> bool {
+		let identity = Self::identity();
+
+		return self
+			.zip_map(&identity, |a, b| (a - b).into().abs())
+			.all(|d| d <= tolerance);
+	}
+
+	pub fn is_orthogonal(&self, tolerance: f32) -> bool {
+		return self
+			.transpose()
+			.mul_mat(self.clone())
+			.is_identity(tolerance);
+	}
+
+	// Singular when elimination finds fewer than N pivots or a pivot within
+	// the tolerance, the same rule for every size
+
+	pub fn is_singular(&self, tolerance: f32) -> bool {
+		let (echelon, record) = self.row_echelon_with_record();
+
+		if record.rank() < N {
+			return true;
+		}
+
+		return record
+			.pivots
+			.iter()
+			.any(|&(i, j)| echelon.data[i][j].into().abs() <= tolerance);
+	}
+
+	pub fn is_invertible(&self, tolerance: f32) -> bool {
+		return !self.is_singular(tolerance);
+	}
+
+	pub fn is_positive_definite(&self, tolerance: f32) -> bool {
+		if !self.is_symmetric(tolerance) {
+			return false;
+		}
+
+		// Cholesky factorization succeeds iff every pivot is positive
+
+		let mut lower: Matrix<K, N, N> = Matrix::zeros();
+
+		for j in 0..N {
+			let mut pivot = self.data[j][j];
+
+			for k in 0..j {
+				pivot = (-lower.data[j][k]).mul_add(lower.data[j][k], pivot);
+			}
+
+			if pivot.into() <= tolerance {
+				return false;
+			}
+
+			lower.data[j][j] = K::from(pivot.into().sqrt());
+
+			for i in j + 1..N {
+				let mut sum = self.data[i][j];
+
+				for k in 0..j {
+					sum = (-lower.data[i][k]).mul_add(lower.data[j][k], sum);
+				}
+				lower.data[i][j] = sum / lower.data[j][j];
+			}
+		}
+
+		return true;
+	}
+}
+
+impl<K: Traits, const M: usize, const N: usize> Clone for Matrix<K, M, N> {
+	fn clone(&self) -> Self {
+		return Self::new(self.data);
 	}
 }
 
@@ -310,7 +1029,7 @@ impl<K: Traits, const M: usize, const N: usize> Add for Matrix<K, M, N> {
 	type Output = Self;
 
 	fn add(self, v: Self) -> Self::Output {
-		let mut result = Self::new([[K::default(); N]; M]);
+		let mut result = Self::zeros();
 
 		for i in 0..M {
 			for j in 0..N {
@@ -336,7 +1055,7 @@ impl<K: Traits, const M: usize, const N: usize> Sub for Matrix<K, M, N> {
 	type Output = Self;
 
 	fn sub(self, v: Self) -> Self::Output {
-		let mut result = Self::new([[K::default(); N]; M]);
+		let mut result = Self::zeros();
 
 		for i in 0..M {
 			for j in 0..N {
@@ -362,7 +1081,7 @@ impl<K: Traits, const M: usize, const N: usize> Mul<K> for Matrix<K, M, N> {
 	type Output = Self;
 
 	fn mul(self, a: K) -> Self::Output {
-		let mut result = Self::new([[K::default(); N]; M]);
+		let mut result = Self::zeros();
 
 		for i in 0..M {
 			for j in 0..N {
@@ -390,15 +1109,16 @@ impl<K: Traits, const M: usize, const N: usize, const P: usize> Mul<Matrix<K, N,
 	type Output = Matrix<K, M, P>;
 
 	fn mul(self, v: Matrix<K, N, P>) -> Matrix<K, M, P> {
-		let mut result: Matrix<K, M, P> = Matrix::new([[K::default(); P]; M]);
-
-		for i in 0..M {
-			for j in 0..P {
-				for k in 0..N {
-					result.data[i][j] = self.data[i][k].mul_add(v.data[k][j], result.data[i][j]);
-				}
-			}
-		}
+		let mut result: Matrix<K, M, P> = Matrix::zeros();
+
+		parallel::gemm(
+			self.data.as_flattened(),
+			v.data.as_flattened(),
+			result.data.as_flattened_mut(),
+			M,
+			N,
+			P,
+		);
 		result
 	}
 }
@@ -407,7 +1127,7 @@ impl<K: Traits, const M: usize, const N: usize> Mul<Vector<K, N>> for Matrix<K,
 	type Output = Matrix<K, M, 1>;
 
 	fn mul(self, v: Vector<K, N>) -> Matrix<K, M, 1> {
-		let mut result: Matrix<K, M, 1> = Matrix::new([[K::default(); 1]; M]);
+		let mut result: Matrix<K, M, 1> = Matrix::zeros();
 
 		for i in 0..M {
 			for k in 0..N {
@@ -0,0 +1,619 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   npy.rs                                             :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:18:00 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 04:11:12 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::checksum::crc32;
+use crate::matrix::Matrix;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+#[derive(Debug)]
+pub enum NpyError {
+	Io(io::Error),
+	Magic,
+	Header(String),
+	Unsupported(String),
+	Shape {
+		expected: Vec<usize>,
+		found: Vec<usize>,
+	},
+	Archive(String),
+	Checksum {
+		expected: u32,
+		found: u32,
+	},
+	Precision {
+		index: usize,
+		value: f64,
+	},
+}
+
+impl fmt::Display for NpyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			NpyError::Io(error) => write!(f, "{}", error),
+			NpyError::Magic => write!(f, "not a .npy file"),
+			NpyError::Header(message) => write!(f, "invalid header: {}", message),
+			NpyError::Unsupported(what) => write!(f, "unsupported {}", what),
+			NpyError::Shape { expected, found } => {
+				write!(f, "expected shape {:?}, found {:?}", expected, found)
+			}
+			NpyError::Archive(message) => write!(f, "invalid archive: {}", message),
+			NpyError::Checksum { expected, found } => {
+				write!(f, "checksum {:08x}, expected {:08x}", found, expected)
+			}
+			NpyError::Precision { index, value } => {
+				write!(
+					f,
+					"value {} at index {} does not fit in f32 exactly",
+					value, index
+				)
+			}
+		}
+	}
+}
+
+impl Error for NpyError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			NpyError::Io(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for NpyError {
+	fn from(error: io::Error) -> Self {
+		NpyError::Io(error)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dtype {
+	F4,
+	F8,
+}
+
+// dtype and fortran_order apply to writing, exact to reading
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NpyOptions {
+	pub dtype: Dtype,
+	pub fortran_order: bool,
+	pub exact: bool,
+}
+
+impl Default for NpyOptions {
+	fn default() -> Self {
+		Self {
+			dtype: Dtype::F4,
+			fortran_order: false,
+			exact: false,
+		}
+	}
+}
+
+// Values are always held in C (row-major) order
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpyArray<K> {
+	pub shape: Vec<usize>,
+	pub data: Vec<K>,
+}
+
+// Moves values from Fortran to C order, or from C to Fortran order when
+// `to_fortran` is set: the index is decomposed fastest-dimension-first in
+// the source order and recomposed slowest-first in the other one
+
+fn reorder<K: Copy>(shape: &[usize], data: &[K], to_fortran: bool) -> Vec<K> {
+	let mut result = data.to_vec();
+	let dims: Vec<usize> = match to_fortran {
+		true => shape.iter().rev().copied().collect(),
+		false => shape.to_vec(),
+	};
+	let mut index = vec![0; dims.len()];
+
+	for (i, &value) in data.iter().enumerate() {
+		let mut rest = i;
+		let mut position = 0;
+		let mut stride = 1;
+
+		for k in 0..dims.len() {
+			index[k] = rest % dims[k];
+			rest /= dims[k];
+		}
+		for k in (0..dims.len()).rev() {
+			position += index[k] * stride;
+			stride *= dims[k];
+		}
+		result[position] = value;
+	}
+
+	return result;
+}
+
+fn field<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+	let missing = || NpyError::Header(format!("missing '{}'", key));
+	let start = header
+		.find(&format!("'{}'", key))
+		.or_else(|| header.find(&format!("\"{}\"", key)))
+		.ok_or_else(missing)?;
+	let rest = header[start + key.len() + 2..].trim_start();
+
+	return rest
+		.strip_prefix(':')
+		.map(|rest| rest.trim_start())
+		.ok_or_else(missing);
+}
+
+fn parse_header(header: &str) -> Result<(bool, Dtype, bool, Vec<usize>), NpyError> {
+	let descr = field(header, "descr")?;
+	let quote = descr.chars().next().unwrap_or(' ');
+	let descr = descr[1..]
+		.split(quote)
+		.next()
+		.ok_or_else(|| NpyError::Header(String::from("invalid 'descr'")))?;
+
+	let (big_endian, dtype) = match descr {
+		"<f4" => (false, Dtype::F4),
+		"<f8" => (false, Dtype::F8),
+		">f4" => (true, Dtype::F4),
+		">f8" => (true, Dtype::F8),
+		other => return Err(NpyError::Unsupported(format!("dtype '{}'", other))),
+	};
+
+	let fortran_order = match field(header, "fortran_order")? {
+		order if order.starts_with("True") => true,
+		order if order.starts_with("False") => false,
+		_ => return Err(NpyError::Header(String::from("invalid 'fortran_order'"))),
+	};
+
+	let shape = field(header, "shape")?;
+	let shape = shape
+		.strip_prefix('(')
+		.and_then(|shape| shape.split(')').next())
+		.ok_or_else(|| NpyError::Header(String::from("invalid 'shape'")))?;
+	let shape = shape
+		.split(',')
+		.map(|dim| dim.trim())
+		.filter(|dim| !dim.is_empty())
+		.map(|dim| dim.parse::<usize>())
+		.collect::<Result<Vec<usize>, _>>()
+		.map_err(|_| NpyError::Header(String::from("invalid 'shape'")))?;
+
+	return Ok((big_endian, dtype, fortran_order, shape));
+}
+
+// Sizes in the header are only trusted as far as the data actually read.
+// Values are held as f32, so '<f8' and '>f8' values are rounded to the
+// nearest one. With options.exact, the first value that would be rounded
+// is reported as NpyError::Precision instead.
+
+pub fn read_npy<K: Traits, R: Read>(reader: R) -> Result<NpyArray<K>, NpyError> {
+	return read_npy_with(reader, &NpyOptions::default());
+}
+
+pub fn read_npy_with<K: Traits, R: Read>(
+	mut reader: R,
+	options: &NpyOptions,
+) -> Result<NpyArray<K>, NpyError> {
+	let mut prelude = [0u8; 8];
+
+	reader.read_exact(&mut prelude)?;
+	if &prelude[..6] != MAGIC {
+		return Err(NpyError::Magic);
+	}
+
+	let length = match prelude[6] {
+		1 => {
+			let mut length = [0u8; 2];
+			reader.read_exact(&mut length)?;
+			u16::from_le_bytes(length) as u64
+		}
+		2 | 3 => {
+			let mut length = [0u8; 4];
+			reader.read_exact(&mut length)?;
+			u32::from_le_bytes(length) as u64
+		}
+		major => {
+			return Err(NpyError::Unsupported(format!(
+				"version {}.{}",
+				major, prelude[7]
+			)))
+		}
+	};
+
+	let header = read_bytes(&mut reader, length)?;
+	let header =
+		String::from_utf8(header).map_err(|_| NpyError::Header(String::from("not UTF-8")))?;
+	let (big_endian, dtype, fortran_order, shape) = parse_header(&header)?;
+	let size = match dtype {
+		Dtype::F4 => 4,
+		Dtype::F8 => 8,
+	};
+	let length = shape
+		.iter()
+		.try_fold(size, |acc: u64, &dim| acc.checked_mul(dim as u64))
+		.ok_or_else(|| NpyError::Header(format!("shape {:?} is too large", shape)))?;
+	let payload = read_bytes(&mut reader, length)?;
+	let mut data: Vec<K> = Vec::with_capacity(payload.len() / size as usize);
+
+	match dtype {
+		Dtype::F4 => {
+			for bytes in payload.chunks_exact(4) {
+				let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+				data.push(K::from(match big_endian {
+					true => f32::from_be_bytes(bytes),
+					false => f32::from_le_bytes(bytes),
+				}));
+			}
+		}
+		Dtype::F8 => {
+			for (index, bytes) in payload.chunks_exact(8).enumerate() {
+				let mut array = [0u8; 8];
+
+				array.copy_from_slice(bytes);
+
+				let value = match big_endian {
+					true => f64::from_be_bytes(array),
+					false => f64::from_le_bytes(array),
+				};
+
+				if options.exact && value as f32 as f64 != value && !value.is_nan() {
+					return Err(NpyError::Precision { index, value });
+				}
+				data.push(K::from(value as f32));
+			}
+		}
+	}
+
+	if fortran_order {
+		data = reorder(&shape, &data, false);
+	}
+
+	return Ok(NpyArray { shape, data });
+}
+
+// Exactly `length` bytes, growing the buffer as they arrive
+
+fn read_bytes<R: Read>(reader: &mut R, length: u64) -> Result<Vec<u8>, NpyError> {
+	let mut result = Vec::new();
+
+	reader.take(length).read_to_end(&mut result)?;
+	if (result.len() as u64) < length {
+		return Err(NpyError::Io(io::Error::from(io::ErrorKind::UnexpectedEof)));
+	}
+
+	return Ok(result);
+}
+
+pub fn write_npy<K: Traits, W: Write>(
+	mut writer: W,
+	array: &NpyArray<K>,
+	options: &NpyOptions,
+) -> Result<(), NpyError> {
+	let shape = match array.shape.len() {
+		1 => format!("({},)", array.shape[0]),
+		_ => {
+			let dims: Vec<String> = array.shape.iter().map(|dim| dim.to_string()).collect();
+			format!("({})", dims.join(", "))
+		}
+	};
+	let descr = match options.dtype {
+		Dtype::F4 => "<f4",
+		Dtype::F8 => "<f8",
+	};
+	let mut header = format!(
+		"{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+		descr,
+		if options.fortran_order {
+			"True"
+		} else {
+			"False"
+		},
+		shape
+	);
+
+	// Pad with spaces and a newline so that the data starts 64-byte aligned
+
+	let version: u8 = if header.len() + 11 <= u16::MAX as usize {
+		1
+	} else {
+		2
+	};
+	let prelude = if version == 1 { 10 } else { 12 };
+	let padding = (64 - (prelude + header.len() + 1) % 64) % 64;
+
+	header.push_str(&" ".repeat(padding));
+	header.push('\n');
+
+	writer.write_all(MAGIC)?;
+	writer.write_all(&[version, 0])?;
+	if version == 1 {
+		writer.write_all(&(header.len() as u16).to_le_bytes())?;
+	} else {
+		writer.write_all(&(header.len() as u32).to_le_bytes())?;
+	}
+	writer.write_all(header.as_bytes())?;
+
+	let data = match options.fortran_order {
+		true => reorder(&array.shape, &array.data, true),
+		false => array.data.clone(),
+	};
+
+	for value in data {
+		match options.dtype {
+			Dtype::F4 => writer.write_all(&value.into().to_le_bytes())?,
+			Dtype::F8 => writer.write_all(&(value.into() as f64).to_le_bytes())?,
+		}
+	}
+
+	return Ok(());
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> Result<u16, NpyError> {
+	return bytes
+		.get(at..at.saturating_add(2))
+		.map(|b| u16::from_le_bytes([b[0], b[1]]))
+		.ok_or_else(|| NpyError::Archive(String::from("truncated")));
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Result<u32, NpyError> {
+	return bytes
+		.get(at..at.saturating_add(4))
+		.map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		.ok_or_else(|| NpyError::Archive(String::from("truncated")));
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> Result<u64, NpyError> {
+	let low = u32_at(bytes, at)? as u64;
+	let high = u32_at(bytes, at.saturating_add(4))? as u64;
+
+	return Ok(low | high << 32);
+}
+
+// Reads the stored (uncompressed) .npy members of an .npz archive, named
+// without their extension. Sizes and offsets come from the central
+// directory, including zip64 ones as written by `numpy.savez`. Deflated
+// members, as written by `numpy.savez_compressed`, are not supported and
+// give NpyError::Unsupported.
+
+pub fn read_npz<K: Traits, R: Read>(mut reader: R) -> Result<Vec<(String, NpyArray<K>)>, NpyError> {
+	let mut bytes = Vec::new();
+
+	reader.read_to_end(&mut bytes)?;
+
+	let end = (0..bytes.len().saturating_sub(21))
+		.rev()
+		.find(|&at| bytes[at..at + 4] == [0x50, 0x4B, 0x05, 0x06])
+		.ok_or_else(|| NpyError::Archive(String::from("no end of central directory")))?;
+	let count = u16_at(&bytes, end + 10)? as usize;
+	let mut at = u32_at(&bytes, end + 16)? as usize;
+	let mut result = Vec::with_capacity(count);
+
+	for _ in 0..count {
+		if u32_at(&bytes, at)? != 0x0201_4B50 {
+			return Err(NpyError::Archive(String::from("bad central directory")));
+		}
+
+		let method = u16_at(&bytes, at + 10)?;
+		let crc = u32_at(&bytes, at + 16)?;
+		let mut size = u32_at(&bytes, at + 20)? as u64;
+		let name_length = u16_at(&bytes, at + 28)? as usize;
+		let extra_length = u16_at(&bytes, at + 30)? as usize;
+		let comment_length = u16_at(&bytes, at + 32)? as usize;
+		let mut offset = u32_at(&bytes, at + 42)? as u64;
+		let name = bytes
+			.get(at + 46..at + 46 + name_length)
+			.ok_or_else(|| NpyError::Archive(String::from("truncated")))?;
+		let name = String::from_utf8_lossy(name).into_owned();
+
+		// Zip64 extra field: the 64-bit values replace the saturated ones
+
+		let mut extra = at + 46 + name_length;
+		let extra_end = extra + extra_length;
+
+		while extra + 4 <= extra_end {
+			let id = u16_at(&bytes, extra)?;
+			let length = u16_at(&bytes, extra + 2)? as usize;
+			let mut field = extra + 4;
+
+			if id == 0x0001 {
+				if u32_at(&bytes, at + 24)? == u32::MAX {
+					field += 8;
+				}
+				if size == u32::MAX as u64 {
+					size = u64_at(&bytes, field)?;
+					field += 8;
+				}
+				if offset == u32::MAX as u64 {
+					offset = u64_at(&bytes, field)?;
+				}
+			}
+			extra += 4 + length;
+		}
+
+		match method {
+			0 => {}
+			8 => {
+				return Err(NpyError::Unsupported(format!(
+					"deflated member '{}', only uncompressed archives (numpy.savez) can be read",
+					name
+				)))
+			}
+			_ => {
+				return Err(NpyError::Unsupported(format!(
+					"compression method {} in '{}'",
+					method, name
+				)))
+			}
+		}
+
+		let local = usize::try_from(offset).unwrap_or(usize::MAX);
+
+		if u32_at(&bytes, local)? != 0x0403_4B50 {
+			return Err(NpyError::Archive(String::from("bad local header")));
+		}
+
+		let start = local
+			+ 30 + u16_at(&bytes, local + 26)? as usize
+			+ u16_at(&bytes, local + 28)? as usize;
+		let data = usize::try_from(size)
+			.ok()
+			.and_then(|size| bytes.get(start..start.checked_add(size)?))
+			.ok_or_else(|| NpyError::Archive(String::from("truncated")))?;
+		let found = crc32(data);
+
+		if found != crc {
+			return Err(NpyError::Checksum {
+				expected: crc,
+				found,
+			});
+		}
+
+		let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+
+		result.push((name, read_npy(data)?));
+		at = extra_end + comment_length;
+	}
+
+	return Ok(result);
+}
+
+// Only the classic zip records are written, so members, offsets and the
+// central directory must each stay under 4 GiB
+
+pub fn write_npz<K: Traits, W: Write>(
+	mut writer: W,
+	arrays: &[(&str, &NpyArray<K>)],
+	options: &NpyOptions,
+) -> Result<(), NpyError> {
+	let too_large = || NpyError::Unsupported(String::from("archive over 4 GiB (zip64)"));
+	let mut central = Vec::new();
+	let mut offset = 0usize;
+
+	let count = u16::try_from(arrays.len())
+		.map_err(|_| NpyError::Unsupported(format!("archive of {} members", arrays.len())))?;
+
+	for (name, array) in arrays {
+		let name = format!("{}.npy", name);
+		let mut data = Vec::new();
+
+		write_npy(&mut data, array, options)?;
+
+		let crc = crc32(&data);
+		let size = u32::try_from(data.len()).map_err(|_| too_large())?;
+		let start = u32::try_from(offset).map_err(|_| too_large())?;
+		let name_length = u16::try_from(name.len())
+			.map_err(|_| NpyError::Unsupported(format!("member name '{}'", name)))?;
+		let mut local = Vec::with_capacity(30 + name.len());
+
+		local.extend_from_slice(&0x0403_4B50u32.to_le_bytes());
+		local.extend_from_slice(&20u16.to_le_bytes());
+		local.extend_from_slice(&[0; 8]);
+		local.extend_from_slice(&crc.to_le_bytes());
+		local.extend_from_slice(&size.to_le_bytes());
+		local.extend_from_slice(&size.to_le_bytes());
+		local.extend_from_slice(&name_length.to_le_bytes());
+		local.extend_from_slice(&0u16.to_le_bytes());
+		local.extend_from_slice(name.as_bytes());
+
+		central.extend_from_slice(&0x0201_4B50u32.to_le_bytes());
+		central.extend_from_slice(&20u16.to_le_bytes());
+		central.extend_from_slice(&local[4..30]);
+		central.extend_from_slice(&[0; 10]);
+		central.extend_from_slice(&start.to_le_bytes());
+		central.extend_from_slice(name.as_bytes());
+
+		writer.write_all(&local)?;
+		writer.write_all(&data)?;
+		offset += local.len() + data.len();
+	}
+
+	let central_size = u32::try_from(central.len()).map_err(|_| too_large())?;
+	let central_start = u32::try_from(offset).map_err(|_| too_large())?;
+
+	writer.write_all(&central)?;
+	writer.write_all(&0x0605_4B50u32.to_le_bytes())?;
+	writer.write_all(&[0; 4])?;
+	writer.write_all(&count.to_le_bytes())?;
+	writer.write_all(&count.to_le_bytes())?;
+	writer.write_all(&central_size.to_le_bytes())?;
+	writer.write_all(&central_start.to_le_bytes())?;
+	writer.write_all(&0u16.to_le_bytes())?;
+
+	return Ok(());
+}
+
+impl<K: Traits, const M: usize, const N: usize> Matrix<K, M, N> {
+	pub fn to_npy(&self) -> NpyArray<K> {
+		return NpyArray {
+			shape: vec![M, N],
+			data: self.data.as_flattened().to_vec(),
+		};
+	}
+
+	pub fn from_npy(array: &NpyArray<K>) -> Result<Self, NpyError> {
+		if array.shape != [M, N] {
+			return Err(NpyError::Shape {
+				expected: vec![M, N],
+				found: array.shape.clone(),
+			});
+		}
+
+		return Ok(Matrix::from_fn(|i, j| array.data[i * N + j]));
+	}
+
+	pub fn read_npy<R: Read>(reader: R) -> Result<Self, NpyError> {
+		return Self::from_npy(&read_npy(reader)?);
+	}
+
+	pub fn write_npy<W: Write>(&self, writer: W, options: &NpyOptions) -> Result<(), NpyError> {
+		return write_npy(writer, &self.to_npy(), options);
+	}
+}
+
+// Also accepts (N, 1) and (1, N) arrays
+
+impl<K: Traits, const N: usize> Vector<K, N> {
+	pub fn to_npy(&self) -> NpyArray<K> {
+		return NpyArray {
+			shape: vec![N],
+			data: self.data.to_vec(),
+		};
+	}
+
+	pub fn from_npy(array: &NpyArray<K>) -> Result<Self, NpyError> {
+		if array.shape != [N] && array.shape != [N, 1] && array.shape != [1, N] {
+			return Err(NpyError::Shape {
+				expected: vec![N],
+				found: array.shape.clone(),
+			});
+		}
+
+		return Ok(Vector::new(std::array::from_fn(|i| array.data[i])));
+	}
+
+	pub fn read_npy<R: Read>(reader: R) -> Result<Self, NpyError> {
+		return Self::from_npy(&read_npy(reader)?);
+	}
+
+	pub fn write_npy<W: Write>(&self, writer: W, options: &NpyOptions) -> Result<(), NpyError> {
+		return write_npy(writer, &self.to_npy(), options);
+	}
+}
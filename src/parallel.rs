@@ -0,0 +1,142 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   parallel.rs                                        :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:59:46 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 02:59:46 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// Row-range helpers used by the matrix kernels. Without the `parallel`
+// feature, or below the size threshold, everything runs on the calling
+// thread. Work is only ever split between whole rows, and every row goes
+// through the same sequence of operations, so results do not depend on
+// the number of threads.
+
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::gemm;
+use crate::traits::Traits;
+
+#[cfg(feature = "parallel")]
+static THRESHOLD: AtomicUsize = AtomicUsize::new(256 * 256);
+
+#[cfg(feature = "parallel")]
+pub fn set_threshold(elements: usize) {
+	THRESHOLD.store(elements, Ordering::Relaxed);
+}
+
+#[cfg(feature = "parallel")]
+pub fn threshold() -> usize {
+	return THRESHOLD.load(Ordering::Relaxed);
+}
+
+#[cfg(feature = "parallel")]
+fn workers(rows: usize, elements: usize) -> usize {
+	if elements < threshold() || rows < 2 {
+		return 1;
+	}
+
+	let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+	return available.min(rows);
+}
+
+#[cfg(not(feature = "parallel"))]
+fn workers(_rows: usize, _elements: usize) -> usize {
+	return 1;
+}
+
+pub fn for_each_row<R, F>(rows: &mut [R], row_len: usize, f: F)
+where
+	R: Send,
+	F: Fn(usize, &mut R) + Sync,
+{
+	let threads = workers(rows.len(), rows.len() * row_len);
+
+	if threads <= 1 {
+		for (i, row) in rows.iter_mut().enumerate() {
+			f(i, row);
+		}
+		return;
+	}
+
+	let chunk = rows.len().div_ceil(threads);
+	let f = &f;
+
+	std::thread::scope(|scope| {
+		for (index, block) in rows.chunks_mut(chunk).enumerate() {
+			scope.spawn(move || {
+				for (i, row) in block.iter_mut().enumerate() {
+					f(index * chunk + i, row);
+				}
+			});
+		}
+	});
+}
+
+pub fn for_each_row_pair<R, S, F>(a: &mut [R], b: &mut [S], row_len: usize, f: F)
+where
+	R: Send,
+	S: Send,
+	F: Fn(usize, &mut R, &mut S) + Sync,
+{
+	if a.len() != b.len() {
+		panic!("Error: Arrays sizes are different");
+	}
+
+	let threads = workers(a.len(), a.len() * row_len);
+
+	if threads <= 1 {
+		for (i, (row_a, row_b)) in a.iter_mut().zip(b.iter_mut()).enumerate() {
+			f(i, row_a, row_b);
+		}
+		return;
+	}
+
+	let chunk = a.len().div_ceil(threads);
+	let f = &f;
+
+	std::thread::scope(|scope| {
+		let blocks = a.chunks_mut(chunk).zip(b.chunks_mut(chunk));
+
+		for (index, (block_a, block_b)) in blocks.enumerate() {
+			scope.spawn(move || {
+				for (i, (row_a, row_b)) in block_a.iter_mut().zip(block_b.iter_mut()).enumerate() {
+					f(index * chunk + i, row_a, row_b);
+				}
+			});
+		}
+	});
+}
+
+// Each worker computes a band of rows of C with the blocked kernel
+
+pub fn gemm<K: Traits>(a: &[K], b: &[K], c: &mut [K], m: usize, n: usize, p: usize) {
+	let threads = workers(m, m * p);
+
+	if threads <= 1 || p == 0 {
+		gemm::gemm_blocked(a, b, c, m, n, p);
+		return;
+	}
+
+	if a.len() != m * n || c.len() != m * p {
+		panic!("Error: Matrix sizes are incompatible");
+	}
+
+	let chunk = m.div_ceil(threads);
+
+	std::thread::scope(|scope| {
+		for (index, band) in c.chunks_mut(chunk * p).enumerate() {
+			let rows = band.len() / p;
+			let start = index * chunk;
+			let a_band = &a[start * n..(start + rows) * n];
+
+			scope.spawn(move || gemm::gemm_blocked(a_band, b, band, rows, n, p));
+		}
+	});
+}
@@ -0,0 +1,437 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   parse.rs                                           :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:15:20 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:23:57 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::matrix::Matrix;
+use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+	UnexpectedCharacter(char),
+	UnexpectedToken(String),
+	UnexpectedEnd,
+	InvalidNumber(String),
+	RaggedRow {
+		expected: usize,
+		found: usize,
+	},
+	Dimensions {
+		expected: (usize, usize),
+		found: (usize, usize),
+	},
+	Empty,
+}
+
+// Lines and columns count from 1, columns in characters
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+	pub kind: ParseErrorKind,
+	pub line: usize,
+	pub column: usize,
+}
+
+impl fmt::Display for ParseErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+			ParseErrorKind::UnexpectedToken(token) => write!(f, "unexpected '{}'", token),
+			ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+			ParseErrorKind::InvalidNumber(number) => write!(f, "invalid number '{}'", number),
+			ParseErrorKind::RaggedRow { expected, found } => {
+				write!(f, "row has {} values, expected {}", found, expected)
+			}
+			ParseErrorKind::Dimensions { expected, found } => write!(
+				f,
+				"expected a {}x{} matrix, found {}x{}",
+				expected.0, expected.1, found.0, found.1
+			),
+			ParseErrorKind::Empty => write!(f, "no values"),
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+	}
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Open,
+	Close,
+	Comma,
+	Semicolon,
+	Newline,
+	Number(f32),
+	End,
+}
+
+impl fmt::Display for Token {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Token::Open => write!(f, "["),
+			Token::Close => write!(f, "]"),
+			Token::Comma => write!(f, ","),
+			Token::Semicolon => write!(f, ";"),
+			Token::Newline => write!(f, "newline"),
+			Token::Number(x) => write!(f, "{}", x),
+			Token::End => write!(f, "end of input"),
+		}
+	}
+}
+
+struct Parser {
+	tokens: Vec<(Token, usize, usize)>,
+	position: usize,
+}
+
+fn tokenize(s: &str) -> Result<Vec<(Token, usize, usize)>, ParseError> {
+	let mut tokens = Vec::new();
+	let (mut line, mut column) = (1, 1);
+	let mut chars = s.chars().peekable();
+
+	while let Some(&c) = chars.peek() {
+		let start = (line, column);
+		let token = match c {
+			'[' => Some(Token::Open),
+			']' => Some(Token::Close),
+			',' => Some(Token::Comma),
+			';' => Some(Token::Semicolon),
+			'\n' => Some(Token::Newline),
+			_ if c.is_whitespace() => None,
+			_ if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+				let mut text = String::new();
+
+				while let Some(&c) = chars.peek() {
+					if !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')) {
+						break;
+					}
+					text.push(c);
+					chars.next();
+					column += 1;
+				}
+
+				let value = text.parse::<f32>().map_err(|_| ParseError {
+					kind: ParseErrorKind::InvalidNumber(text.clone()),
+					line: start.0,
+					column: start.1,
+				})?;
+
+				tokens.push((Token::Number(value), start.0, start.1));
+				continue;
+			}
+			_ => {
+				return Err(ParseError {
+					kind: ParseErrorKind::UnexpectedCharacter(c),
+					line,
+					column,
+				})
+			}
+		};
+
+		chars.next();
+		if c == '\n' {
+			line += 1;
+			column = 1;
+		} else {
+			column += 1;
+		}
+		if let Some(token) = token {
+			tokens.push((token, start.0, start.1));
+		}
+	}
+
+	tokens.push((Token::End, line, column));
+
+	return Ok(tokens);
+}
+
+impl Parser {
+	fn peek(&self) -> &Token {
+		return &self.tokens[self.position].0;
+	}
+
+	fn next(&mut self) -> Token {
+		let token = self.tokens[self.position].0.clone();
+
+		if token != Token::End {
+			self.position += 1;
+		}
+
+		return token;
+	}
+
+	fn skip_newlines(&mut self) {
+		while *self.peek() == Token::Newline {
+			self.position += 1;
+		}
+	}
+
+	fn error(&self, kind: ParseErrorKind) -> ParseError {
+		let (_, line, column) = self.tokens[self.position];
+
+		return ParseError { kind, line, column };
+	}
+
+	fn unexpected(&self) -> ParseError {
+		return match self.peek() {
+			Token::End => self.error(ParseErrorKind::UnexpectedEnd),
+			token => self.error(ParseErrorKind::UnexpectedToken(token.to_string())),
+		};
+	}
+
+	fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+		if *self.peek() != token {
+			return Err(self.unexpected());
+		}
+		self.next();
+
+		return Ok(());
+	}
+
+	// [[1, 2], [3, 4]]
+
+	fn nested(&mut self) -> Result<Vec<(Vec<f32>, usize)>, ParseError> {
+		let mut rows = Vec::new();
+
+		self.expect(Token::Open)?;
+		loop {
+			self.skip_newlines();
+
+			let start = self.position;
+
+			self.expect(Token::Open)?;
+
+			let mut row = Vec::new();
+
+			loop {
+				self.skip_newlines();
+				match self.peek() {
+					Token::Number(x) => row.push(*x),
+					_ => return Err(self.unexpected()),
+				}
+				self.next();
+				self.skip_newlines();
+				match self.peek() {
+					Token::Comma => {
+						self.next();
+					}
+					Token::Close => break,
+					_ => return Err(self.unexpected()),
+				}
+			}
+			self.expect(Token::Close)?;
+			rows.push((row, start));
+
+			self.skip_newlines();
+			match self.peek() {
+				Token::Comma => {
+					self.next();
+				}
+				Token::Close => break,
+				_ => return Err(self.unexpected()),
+			}
+		}
+		self.expect(Token::Close)?;
+
+		return Ok(rows);
+	}
+
+	// [1 2; 3 4] when bracketed, else rows on separate lines
+
+	fn flat(&mut self, bracketed: bool) -> Result<Vec<(Vec<f32>, usize)>, ParseError> {
+		let mut rows = Vec::new();
+		let mut row = Vec::new();
+		let mut start = self.position;
+
+		if bracketed {
+			self.expect(Token::Open)?;
+			start = self.position;
+		}
+
+		loop {
+			match self.peek() {
+				Token::Number(x) => {
+					if row.is_empty() {
+						start = self.position;
+					}
+					row.push(*x);
+					self.next();
+				}
+				Token::Comma if !row.is_empty() => {
+					self.next();
+					if !matches!(self.peek(), Token::Number(_)) {
+						return Err(self.unexpected());
+					}
+				}
+				Token::Semicolon | Token::Newline => {
+					if !row.is_empty() {
+						rows.push((std::mem::take(&mut row), start));
+					}
+					self.next();
+				}
+				Token::Close if bracketed => {
+					self.next();
+					break;
+				}
+				Token::End if !bracketed => break,
+				_ => return Err(self.unexpected()),
+			}
+		}
+		if !row.is_empty() {
+			rows.push((row, start));
+		}
+
+		return Ok(rows);
+	}
+
+	fn parse(&mut self) -> Result<Vec<Vec<f32>>, ParseError> {
+		self.skip_newlines();
+
+		let nested = *self.peek() == Token::Open && {
+			let mut i = self.position + 1;
+
+			while self.tokens[i].0 == Token::Newline {
+				i += 1;
+			}
+			self.tokens[i].0 == Token::Open
+		};
+		let rows = match (nested, *self.peek() == Token::Open) {
+			(true, _) => self.nested()?,
+			(false, bracketed) => self.flat(bracketed)?,
+		};
+
+		self.skip_newlines();
+		if *self.peek() != Token::End {
+			return Err(self.unexpected());
+		}
+
+		if rows.is_empty() {
+			self.position = 0;
+			return Err(self.error(ParseErrorKind::Empty));
+		}
+
+		let expected = rows[0].0.len();
+
+		for (row, start) in &rows {
+			if row.len() != expected {
+				self.position = *start;
+				return Err(self.error(ParseErrorKind::RaggedRow {
+					expected,
+					found: row.len(),
+				}));
+			}
+		}
+
+		return Ok(rows.into_iter().map(|(row, _)| row).collect());
+	}
+}
+
+pub fn parse_rows<K: Traits>(s: &str) -> Result<Vec<Vec<K>>, ParseError> {
+	let mut parser = Parser {
+		tokens: tokenize(s)?,
+		position: 0,
+	};
+	let rows = parser.parse()?;
+
+	return Ok(rows
+		.into_iter()
+		.map(|row| row.into_iter().map(K::from).collect())
+		.collect());
+}
+
+fn dimensions_error(expected: (usize, usize), found: (usize, usize)) -> ParseError {
+	return ParseError {
+		kind: ParseErrorKind::Dimensions { expected, found },
+		line: 1,
+		column: 1,
+	};
+}
+
+impl<K: Traits, const M: usize, const N: usize> FromStr for Matrix<K, M, N> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let rows = parse_rows::<K>(s)?;
+
+		if rows.len() != M || rows[0].len() != N {
+			return Err(dimensions_error((M, N), (rows.len(), rows[0].len())));
+		}
+
+		return Ok(Matrix::from_fn(|i, j| rows[i][j]));
+	}
+}
+
+// A single row or a single column
+
+impl<K: Traits, const N: usize> FromStr for Vector<K, N> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let rows = parse_rows::<K>(s)?;
+		let values: Vec<K> = match (rows.len(), rows[0].len()) {
+			(1, _) => rows[0].clone(),
+			(_, 1) => rows.iter().map(|row| row[0]).collect(),
+			found => return Err(dimensions_error((1, N), found)),
+		};
+
+		if values.len() != N {
+			return Err(dimensions_error((1, N), (rows.len(), rows[0].len())));
+		}
+
+		return Ok(Vector::new(std::array::from_fn(|i| values[i])));
+	}
+}
+
+impl<K: Traits> FromStr for CooMatrix<K> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let rows = parse_rows::<K>(s)?;
+		let mut result = CooMatrix::new(rows.len(), rows[0].len());
+
+		for (i, row) in rows.iter().enumerate() {
+			for (j, &value) in row.iter().enumerate() {
+				if value != K::default() {
+					result.push(i, j, value);
+				}
+			}
+		}
+
+		return Ok(result);
+	}
+}
+
+impl<K: Traits> FromStr for CsrMatrix<K> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		return Ok(s.parse::<CooMatrix<K>>()?.to_csr());
+	}
+}
+
+impl<K: Traits> FromStr for CscMatrix<K> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		return Ok(s.parse::<CooMatrix<K>>()?.to_csc());
+	}
+}
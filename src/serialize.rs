@@ -0,0 +1,132 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   serialize.rs                                       :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:19:32 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:19:32 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::matrix::Matrix;
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+// Vectors are sequences of N values, matrices sequences of M rows.
+// Lengths are checked exactly on the way back in.
+
+struct ArrayVisitor<K, const N: usize>(PhantomData<K>);
+
+impl<'de, K, const N: usize> Visitor<'de> for ArrayVisitor<K, N>
+where
+	K: Traits + Deserialize<'de>,
+{
+	type Value = [K; N];
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a sequence of {} values", N)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut result = [K::default(); N];
+
+		for i in 0..N {
+			result[i] = seq
+				.next_element()?
+				.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+		}
+		if seq.next_element::<de::IgnoredAny>()?.is_some() {
+			return Err(de::Error::invalid_length(N + 1, &self));
+		}
+
+		return Ok(result);
+	}
+}
+
+struct Row<K, const N: usize>([K; N]);
+
+impl<'de, K, const N: usize> Deserialize<'de> for Row<K, N>
+where
+	K: Traits + Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		return deserializer
+			.deserialize_seq(ArrayVisitor::<K, N>(PhantomData))
+			.map(Row);
+	}
+}
+
+struct MatrixVisitor<K, const M: usize, const N: usize>(PhantomData<K>);
+
+impl<'de, K, const M: usize, const N: usize> Visitor<'de> for MatrixVisitor<K, M, N>
+where
+	K: Traits + Deserialize<'de>,
+{
+	type Value = [[K; N]; M];
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a sequence of {} rows of {} values", M, N)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut result = [[K::default(); N]; M];
+
+		for i in 0..M {
+			let row: Row<K, N> = seq
+				.next_element()?
+				.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+
+			result[i] = row.0;
+		}
+		if seq.next_element::<de::IgnoredAny>()?.is_some() {
+			return Err(de::Error::invalid_length(M + 1, &self));
+		}
+
+		return Ok(result);
+	}
+}
+
+impl<K: Traits + Serialize, const N: usize> Serialize for Vector<K, N> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		return self.data[..].serialize(serializer);
+	}
+}
+
+impl<'de, K: Traits + Deserialize<'de>, const N: usize> Deserialize<'de> for Vector<K, N> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let data = deserializer.deserialize_seq(ArrayVisitor::<K, N>(PhantomData))?;
+
+		return Ok(Vector::new(data));
+	}
+}
+
+impl<K: Traits + Serialize, const M: usize, const N: usize> Serialize for Matrix<K, M, N> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut seq = serializer.serialize_seq(Some(M))?;
+
+		for row in &self.data {
+			seq.serialize_element(&row[..])?;
+		}
+
+		return seq.end();
+	}
+}
+
+impl<'de, K, const M: usize, const N: usize> Deserialize<'de> for Matrix<K, M, N>
+where
+	K: Traits + Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let data = deserializer.deserialize_seq(MatrixVisitor::<K, M, N>(PhantomData))?;
+
+		return Ok(Matrix::new(data));
+	}
+}
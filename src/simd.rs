@@ -0,0 +1,294 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   simd.rs                                            :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:01:21 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:41:24 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+// Vectorized kernels for f32 and f64, dispatched at runtime to AVX2+FMA on
+// x86_64 and to NEON on aarch64.
+//
+// axpy keeps one fused multiply-add per element in the same order as the
+// scalar loop, so its results are bit-identical on every CPU. It is reached
+// through MulAdd::axpy, which every scalar type already implements, and is
+// what gemm_blocked and Vector::linear_combination use.
+//
+// Kernels::dot sums in several lanes, so it reassociates the sum and can
+// differ from the sequential mul_add chain in the last bits, depending on
+// the CPU. It is opt-in: Vector::dot and blas::dot keep the sequential
+// chain.
+
+use crate::gemm;
+use crate::traits::MulAdd;
+
+pub trait Kernels: Copy + Default + MulAdd<Output = Self> {
+	fn dot(a: &[Self], b: &[Self]) -> Self {
+		if a.len() != b.len() {
+			panic!("Error: Arrays sizes are different");
+		}
+
+		let mut result = Self::default();
+
+		for i in 0..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	fn gemm(a: &[Self], b: &[Self], c: &mut [Self], m: usize, n: usize, p: usize) {
+		gemm::gemm_blocked(a, b, c, m, n, p);
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx2_fma() -> bool {
+	return is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma");
+}
+
+macro_rules! impl_kernels {
+	($t:ty, $dot:ident, $axpy:ident) => {
+		impl Kernels for $t {
+			fn dot(a: &[$t], b: &[$t]) -> $t {
+				if a.len() != b.len() {
+					panic!("Error: Arrays sizes are different");
+				}
+
+				#[cfg(target_arch = "aarch64")]
+				{
+					return unsafe { neon::$dot(a, b) };
+				}
+
+				#[cfg(not(target_arch = "aarch64"))]
+				{
+					#[cfg(target_arch = "x86_64")]
+					if has_avx2_fma() {
+						return unsafe { x86::$dot(a, b) };
+					}
+
+					return portable::$dot(a, b);
+				}
+			}
+		}
+
+		pub(crate) fn $axpy(alpha: $t, x: &[$t], y: &mut [$t]) {
+			if x.len() != y.len() {
+				panic!("Error: Arrays sizes are different");
+			}
+
+			#[cfg(target_arch = "aarch64")]
+			{
+				return unsafe { neon::$axpy(alpha, x, y) };
+			}
+
+			#[cfg(not(target_arch = "aarch64"))]
+			{
+				#[cfg(target_arch = "x86_64")]
+				if has_avx2_fma() {
+					return unsafe { x86::$axpy(alpha, x, y) };
+				}
+
+				return portable::$axpy(alpha, x, y);
+			}
+		}
+	};
+}
+
+impl_kernels!(f32, dot_f32, axpy_f32);
+impl_kernels!(f64, dot_f64, axpy_f64);
+
+#[cfg(not(target_arch = "aarch64"))]
+mod portable {
+	pub fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+		let mut result = 0.;
+
+		for i in 0..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	pub fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+		let mut result = 0.;
+
+		for i in 0..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	pub fn axpy_f32(alpha: f32, x: &[f32], y: &mut [f32]) {
+		for i in 0..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+
+	pub fn axpy_f64(alpha: f64, x: &[f64], y: &mut [f64]) {
+		for i in 0..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+	use std::arch::x86_64::*;
+
+	#[target_feature(enable = "avx2,fma")]
+	pub unsafe fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+		let lanes = a.len() / 8 * 8;
+		let mut acc = _mm256_setzero_ps();
+
+		for i in (0..lanes).step_by(8) {
+			let va = _mm256_loadu_ps(a.as_ptr().add(i));
+			let vb = _mm256_loadu_ps(b.as_ptr().add(i));
+			acc = _mm256_fmadd_ps(va, vb, acc);
+		}
+
+		let mut partial = [0f32; 8];
+		_mm256_storeu_ps(partial.as_mut_ptr(), acc);
+
+		let mut result = partial.iter().sum::<f32>();
+
+		for i in lanes..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	#[target_feature(enable = "avx2,fma")]
+	pub unsafe fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+		let lanes = a.len() / 4 * 4;
+		let mut acc = _mm256_setzero_pd();
+
+		for i in (0..lanes).step_by(4) {
+			let va = _mm256_loadu_pd(a.as_ptr().add(i));
+			let vb = _mm256_loadu_pd(b.as_ptr().add(i));
+			acc = _mm256_fmadd_pd(va, vb, acc);
+		}
+
+		let mut partial = [0f64; 4];
+		_mm256_storeu_pd(partial.as_mut_ptr(), acc);
+
+		let mut result = partial.iter().sum::<f64>();
+
+		for i in lanes..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	#[target_feature(enable = "avx2,fma")]
+	pub unsafe fn axpy_f32(alpha: f32, x: &[f32], y: &mut [f32]) {
+		let lanes = x.len() / 8 * 8;
+		let va = _mm256_set1_ps(alpha);
+
+		for i in (0..lanes).step_by(8) {
+			let vx = _mm256_loadu_ps(x.as_ptr().add(i));
+			let vy = _mm256_loadu_ps(y.as_ptr().add(i));
+			_mm256_storeu_ps(y.as_mut_ptr().add(i), _mm256_fmadd_ps(vx, va, vy));
+		}
+
+		for i in lanes..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+
+	#[target_feature(enable = "avx2,fma")]
+	pub unsafe fn axpy_f64(alpha: f64, x: &[f64], y: &mut [f64]) {
+		let lanes = x.len() / 4 * 4;
+		let va = _mm256_set1_pd(alpha);
+
+		for i in (0..lanes).step_by(4) {
+			let vx = _mm256_loadu_pd(x.as_ptr().add(i));
+			let vy = _mm256_loadu_pd(y.as_ptr().add(i));
+			_mm256_storeu_pd(y.as_mut_ptr().add(i), _mm256_fmadd_pd(vx, va, vy));
+		}
+
+		for i in lanes..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+	use std::arch::aarch64::*;
+
+	pub unsafe fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+		let lanes = a.len() / 4 * 4;
+		let mut acc = vdupq_n_f32(0.);
+
+		for i in (0..lanes).step_by(4) {
+			let va = vld1q_f32(a.as_ptr().add(i));
+			let vb = vld1q_f32(b.as_ptr().add(i));
+			acc = vfmaq_f32(acc, va, vb);
+		}
+
+		let mut result = vaddvq_f32(acc);
+
+		for i in lanes..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	pub unsafe fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+		let lanes = a.len() / 2 * 2;
+		let mut acc = vdupq_n_f64(0.);
+
+		for i in (0..lanes).step_by(2) {
+			let va = vld1q_f64(a.as_ptr().add(i));
+			let vb = vld1q_f64(b.as_ptr().add(i));
+			acc = vfmaq_f64(acc, va, vb);
+		}
+
+		let mut result = vaddvq_f64(acc);
+
+		for i in lanes..a.len() {
+			result = a[i].mul_add(b[i], result);
+		}
+
+		return result;
+	}
+
+	pub unsafe fn axpy_f32(alpha: f32, x: &[f32], y: &mut [f32]) {
+		let lanes = x.len() / 4 * 4;
+		let va = vdupq_n_f32(alpha);
+
+		for i in (0..lanes).step_by(4) {
+			let vx = vld1q_f32(x.as_ptr().add(i));
+			let vy = vld1q_f32(y.as_ptr().add(i));
+			vst1q_f32(y.as_mut_ptr().add(i), vfmaq_f32(vy, vx, va));
+		}
+
+		for i in lanes..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+
+	pub unsafe fn axpy_f64(alpha: f64, x: &[f64], y: &mut [f64]) {
+		let lanes = x.len() / 2 * 2;
+		let va = vdupq_n_f64(alpha);
+
+		for i in (0..lanes).step_by(2) {
+			let vx = vld1q_f64(x.as_ptr().add(i));
+			let vy = vld1q_f64(y.as_ptr().add(i));
+			vst1q_f64(y.as_mut_ptr().add(i), vfmaq_f64(vy, vx, va));
+		}
+
+		for i in lanes..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
+}
@@ -0,0 +1,540 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   solver.rs                                          :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:57:25 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 02:57:25 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use crate::matrix::Matrix;
+use crate::sparse::{CscMatrix, CsrMatrix};
+use crate::traits::Traits;
+
+pub trait LinearOperator<K> {
+	fn rows(&self) -> usize;
+	fn cols(&self) -> usize;
+	fn mul_slice(&self, x: &[K]) -> Vec<K>;
+	fn diagonal(&self) -> Vec<K>;
+	fn row_entries(&self, row: usize) -> Vec<(usize, K)>;
+}
+
+pub trait Preconditioner<K> {
+	fn apply(&self, r: &[K]) -> Vec<K>;
+}
+
+pub struct JacobiPreconditioner<K> {
+	pub inverse_diagonal: Vec<K>,
+}
+
+pub struct SolverOptions {
+	pub tolerance: f32,
+	pub max_iterations: usize,
+	pub restart: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SolverResult<K> {
+	pub solution: Vec<K>,
+	pub converged: bool,
+	pub iterations: usize,
+	pub residuals: Vec<f32>,
+}
+
+impl Default for SolverOptions {
+	fn default() -> Self {
+		Self {
+			tolerance: 1e-6,
+			max_iterations: 1000,
+			restart: 30,
+		}
+	}
+}
+
+impl<K: Traits> JacobiPreconditioner<K> {
+	pub fn new<A: LinearOperator<K>>(a: &A) -> Self {
+		let mut inverse_diagonal = a.diagonal();
+
+		for value in inverse_diagonal.iter_mut() {
+			if *value == K::default() {
+				panic!("Error: Jacobi preconditioner needs a non-zero diagonal");
+			}
+			*value = K::from(1.) / *value;
+		}
+
+		Self { inverse_diagonal }
+	}
+}
+
+impl<K: Traits> Preconditioner<K> for JacobiPreconditioner<K> {
+	fn apply(&self, r: &[K]) -> Vec<K> {
+		let mut result = r.to_vec();
+
+		for i in 0..result.len() {
+			result[i] *= self.inverse_diagonal[i];
+		}
+
+		return result;
+	}
+}
+
+impl<K: Traits, const M: usize, const N: usize> LinearOperator<K> for Matrix<K, M, N> {
+	fn rows(&self) -> usize {
+		return M;
+	}
+
+	fn cols(&self) -> usize {
+		return N;
+	}
+
+	fn mul_slice(&self, x: &[K]) -> Vec<K> {
+		if x.len() != N {
+			panic!("Error: Matrix and vector sizes are incompatible");
+		}
+
+		let mut result = vec![K::default(); M];
+
+		for i in 0..M {
+			for k in 0..N {
+				result[i] = self.data[i][k].mul_add(x[k], result[i]);
+			}
+		}
+
+		return result;
+	}
+
+	fn diagonal(&self) -> Vec<K> {
+		let mut result = Vec::with_capacity(M.min(N));
+
+		for i in 0..M.min(N) {
+			result.push(self.data[i][i]);
+		}
+
+		return result;
+	}
+
+	fn row_entries(&self, row: usize) -> Vec<(usize, K)> {
+		let mut result = Vec::new();
+
+		for j in 0..N {
+			if self.data[row][j] != K::default() {
+				result.push((j, self.data[row][j]));
+			}
+		}
+
+		return result;
+	}
+}
+
+impl<K: Traits> LinearOperator<K> for CsrMatrix<K> {
+	fn rows(&self) -> usize {
+		return self.rows;
+	}
+
+	fn cols(&self) -> usize {
+		return self.cols;
+	}
+
+	fn mul_slice(&self, x: &[K]) -> Vec<K> {
+		return CsrMatrix::mul_slice(self, x);
+	}
+
+	fn diagonal(&self) -> Vec<K> {
+		let mut result = Vec::with_capacity(self.rows.min(self.cols));
+
+		for i in 0..self.rows.min(self.cols) {
+			result.push(self.get(i, i));
+		}
+
+		return result;
+	}
+
+	fn row_entries(&self, row: usize) -> Vec<(usize, K)> {
+		let mut result = Vec::new();
+
+		for k in self.row_offsets[row]..self.row_offsets[row + 1] {
+			result.push((self.col_indices[k], self.values[k]));
+		}
+
+		return result;
+	}
+}
+
+impl<K: Traits> LinearOperator<K> for CscMatrix<K> {
+	fn rows(&self) -> usize {
+		return self.rows;
+	}
+
+	fn cols(&self) -> usize {
+		return self.cols;
+	}
+
+	fn mul_slice(&self, x: &[K]) -> Vec<K> {
+		return CscMatrix::mul_slice(self, x);
+	}
+
+	fn diagonal(&self) -> Vec<K> {
+		let mut result = Vec::with_capacity(self.rows.min(self.cols));
+
+		for i in 0..self.rows.min(self.cols) {
+			result.push(self.get(i, i));
+		}
+
+		return result;
+	}
+
+	fn row_entries(&self, row: usize) -> Vec<(usize, K)> {
+		let mut result = Vec::new();
+
+		for j in 0..self.cols {
+			for k in self.col_offsets[j]..self.col_offsets[j + 1] {
+				if self.row_indices[k] == row {
+					result.push((j, self.values[k]));
+				}
+			}
+		}
+
+		return result;
+	}
+}
+
+fn dot<K: Traits>(u: &[K], v: &[K]) -> K {
+	let mut result = K::default();
+
+	for i in 0..u.len() {
+		result = u[i].mul_add(v[i], result);
+	}
+
+	return result;
+}
+
+fn norm<K: Traits>(u: &[K]) -> f32 {
+	return dot(u, u).into().sqrt();
+}
+
+fn residual<K: Traits, A: LinearOperator<K>>(a: &A, b: &[K], x: &[K]) -> Vec<K> {
+	let mut result = a.mul_slice(x);
+
+	for i in 0..result.len() {
+		result[i] = b[i] - result[i];
+	}
+
+	return result;
+}
+
+fn check_system<K: Traits, A: LinearOperator<K>>(a: &A, b: &[K]) {
+	if a.rows() != a.cols() {
+		panic!("Error: Iterative solvers need a square matrix");
+	}
+	if b.len() != a.rows() {
+		panic!("Error: Matrix and vector sizes are incompatible");
+	}
+}
+
+fn precondition<K: Traits>(preconditioner: Option<&dyn Preconditioner<K>>, r: &[K]) -> Vec<K> {
+	match preconditioner {
+		Some(m) => m.apply(r),
+		None => r.to_vec(),
+	}
+}
+
+// Tracks the relative residual history shared by every solver
+
+struct Monitor<'a> {
+	options: &'a SolverOptions,
+	b_norm: f32,
+	residuals: Vec<f32>,
+}
+
+impl<'a> Monitor<'a> {
+	fn new<K: Traits>(options: &'a SolverOptions, b: &[K]) -> Self {
+		let b_norm = norm(b);
+
+		Self {
+			options,
+			b_norm: if b_norm == 0. { 1. } else { b_norm },
+			residuals: Vec::new(),
+		}
+	}
+
+	fn record(&mut self, residual_norm: f32) -> bool {
+		let relative = residual_norm / self.b_norm;
+		self.residuals.push(relative);
+
+		return relative <= self.options.tolerance;
+	}
+
+	fn finish<K>(self, solution: Vec<K>, converged: bool) -> SolverResult<K> {
+		SolverResult {
+			solution,
+			converged,
+			iterations: self.residuals.len() - 1,
+			residuals: self.residuals,
+		}
+	}
+}
+
+pub fn conjugate_gradient<K: Traits, A: LinearOperator<K>>(
+	a: &A,
+	b: &[K],
+	options: &SolverOptions,
+	preconditioner: Option<&dyn Preconditioner<K>>,
+) -> SolverResult<K> {
+	check_system(a, b);
+
+	let mut monitor = Monitor::new(options, b);
+	let mut x = vec![K::default(); b.len()];
+	let mut r = b.to_vec();
+
+	if monitor.record(norm(&r)) {
+		return monitor.finish(x, true);
+	}
+
+	let mut z = precondition(preconditioner, &r);
+	let mut p = z.clone();
+	let mut rz = dot(&r, &z);
+
+	for _ in 0..options.max_iterations {
+		let ap = a.mul_slice(&p);
+		let alpha = rz / dot(&p, &ap);
+
+		for i in 0..x.len() {
+			x[i] = alpha.mul_add(p[i], x[i]);
+			r[i] = (-alpha).mul_add(ap[i], r[i]);
+		}
+
+		if monitor.record(norm(&r)) {
+			return monitor.finish(x, true);
+		}
+
+		z = precondition(preconditioner, &r);
+
+		let next_rz = dot(&r, &z);
+		let beta = next_rz / rz;
+
+		for i in 0..p.len() {
+			p[i] = beta.mul_add(p[i], z[i]);
+		}
+
+		rz = next_rz;
+	}
+
+	return monitor.finish(x, false);
+}
+
+// Restarted GMRES with right preconditioning, so the recorded residuals
+// are the residuals of the original system
+
+pub fn gmres<K: Traits, A: LinearOperator<K>>(
+	a: &A,
+	b: &[K],
+	options: &SolverOptions,
+	preconditioner: Option<&dyn Preconditioner<K>>,
+) -> SolverResult<K> {
+	check_system(a, b);
+
+	let n = b.len();
+	let restart = options.restart.clamp(1, n.max(1));
+	let mut monitor = Monitor::new(options, b);
+	let mut x = vec![K::default(); n];
+	let mut iterations = 0;
+
+	let mut r = residual(a, b, &x);
+	let mut beta = norm(&r);
+
+	if monitor.record(beta) {
+		return monitor.finish(x, true);
+	}
+
+	while iterations < options.max_iterations {
+		let mut basis: Vec<Vec<K>> = Vec::with_capacity(restart + 1);
+		let mut h = vec![vec![K::default(); restart]; restart + 1];
+		let mut cs = vec![K::default(); restart];
+		let mut sn = vec![K::default(); restart];
+		let mut g = vec![K::default(); restart + 1];
+		let mut converged = false;
+		let mut k = 0;
+
+		let inverse_beta = K::from(1. / beta);
+		basis.push(r.iter().map(|&value| value * inverse_beta).collect());
+		g[0] = K::from(beta);
+
+		while k < restart && iterations < options.max_iterations {
+			// Arnoldi step with modified Gram-Schmidt
+
+			let mut w = a.mul_slice(&precondition(preconditioner, &basis[k]));
+
+			for i in 0..=k {
+				h[i][k] = dot(&w, &basis[i]);
+				for l in 0..n {
+					w[l] = (-h[i][k]).mul_add(basis[i][l], w[l]);
+				}
+			}
+
+			let w_norm = norm(&w);
+			h[k + 1][k] = K::from(w_norm);
+
+			if w_norm != 0. {
+				let inverse_norm = K::from(1. / w_norm);
+				basis.push(w.iter().map(|&value| value * inverse_norm).collect());
+			} else {
+				basis.push(vec![K::default(); n]);
+			}
+
+			// Apply the previous Givens rotations, then eliminate h[k + 1][k]
+
+			for i in 0..k {
+				let temp = cs[i].mul_add(h[i][k], sn[i] * h[i + 1][k]);
+				h[i + 1][k] = (-sn[i]).mul_add(h[i][k], cs[i] * h[i + 1][k]);
+				h[i][k] = temp;
+			}
+
+			let hypot = h[k][k].into().hypot(h[k + 1][k].into());
+			if hypot == 0. {
+				cs[k] = K::from(1.);
+				sn[k] = K::default();
+			} else {
+				cs[k] = h[k][k] / K::from(hypot);
+				sn[k] = h[k + 1][k] / K::from(hypot);
+			}
+
+			h[k][k] = K::from(hypot);
+			h[k + 1][k] = K::default();
+			g[k + 1] = -sn[k] * g[k];
+			g[k] = cs[k] * g[k];
+
+			k += 1;
+			iterations += 1;
+
+			if monitor.record(g[k].into().abs()) || w_norm == 0. {
+				converged = true;
+				break;
+			}
+		}
+
+		// Back substitution on the triangular Hessenberg system
+
+		let mut y = vec![K::default(); k];
+
+		for i in (0..k).rev() {
+			let mut sum = g[i];
+
+			for j in i + 1..k {
+				sum = (-h[i][j]).mul_add(y[j], sum);
+			}
+			y[i] = sum / h[i][i];
+		}
+
+		let mut update = vec![K::default(); n];
+
+		for j in 0..k {
+			for l in 0..n {
+				update[l] = y[j].mul_add(basis[j][l], update[l]);
+			}
+		}
+
+		let update = precondition(preconditioner, &update);
+
+		for l in 0..n {
+			x[l] += update[l];
+		}
+
+		if converged {
+			return monitor.finish(x, true);
+		}
+
+		r = residual(a, b, &x);
+		beta = norm(&r);
+	}
+
+	return monitor.finish(x, false);
+}
+
+pub fn jacobi<K: Traits, A: LinearOperator<K>>(
+	a: &A,
+	b: &[K],
+	options: &SolverOptions,
+) -> SolverResult<K> {
+	check_system(a, b);
+
+	let diagonal = a.diagonal();
+	let rows: Vec<Vec<(usize, K)>> = (0..a.rows()).map(|i| a.row_entries(i)).collect();
+	let mut monitor = Monitor::new(options, b);
+	let mut x = vec![K::default(); b.len()];
+
+	if diagonal.contains(&K::default()) {
+		panic!("Error: Jacobi iteration needs a non-zero diagonal");
+	}
+
+	if monitor.record(norm(b)) {
+		return monitor.finish(x, true);
+	}
+
+	for _ in 0..options.max_iterations {
+		let mut next = vec![K::default(); x.len()];
+
+		for i in 0..x.len() {
+			let mut sum = b[i];
+
+			for &(j, value) in &rows[i] {
+				if j != i {
+					sum = (-value).mul_add(x[j], sum);
+				}
+			}
+			next[i] = sum / diagonal[i];
+		}
+
+		x = next;
+
+		if monitor.record(norm(&residual(a, b, &x))) {
+			return monitor.finish(x, true);
+		}
+	}
+
+	return monitor.finish(x, false);
+}
+
+pub fn gauss_seidel<K: Traits, A: LinearOperator<K>>(
+	a: &A,
+	b: &[K],
+	options: &SolverOptions,
+) -> SolverResult<K> {
+	check_system(a, b);
+
+	let diagonal = a.diagonal();
+	let rows: Vec<Vec<(usize, K)>> = (0..a.rows()).map(|i| a.row_entries(i)).collect();
+	let mut monitor = Monitor::new(options, b);
+	let mut x = vec![K::default(); b.len()];
+
+	if diagonal.contains(&K::default()) {
+		panic!("Error: Gauss-Seidel iteration needs a non-zero diagonal");
+	}
+
+	if monitor.record(norm(b)) {
+		return monitor.finish(x, true);
+	}
+
+	for _ in 0..options.max_iterations {
+		// Updated values are used as soon as they are available
+
+		for i in 0..x.len() {
+			let mut sum = b[i];
+
+			for &(j, value) in &rows[i] {
+				if j != i {
+					sum = (-value).mul_add(x[j], sum);
+				}
+			}
+			x[i] = sum / diagonal[i];
+		}
+
+		if monitor.record(norm(&residual(a, b, &x))) {
+			return monitor.finish(x, true);
+		}
+	}
+
+	return monitor.finish(x, false);
+}
@@ -0,0 +1,461 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   sparse.rs                                          :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 02:56:27 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:05:57 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use std::ops::Add;
+
+use crate::matrix::Matrix;
+use crate::solver::{self, SolverOptions};
+use crate::traits::Traits;
+use crate::vector::Vector;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<K> {
+	pub rows: usize,
+	pub cols: usize,
+	pub entries: Vec<(usize, usize, K)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<K> {
+	pub rows: usize,
+	pub cols: usize,
+	pub row_offsets: Vec<usize>,
+	pub col_indices: Vec<usize>,
+	pub values: Vec<K>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<K> {
+	pub rows: usize,
+	pub cols: usize,
+	pub col_offsets: Vec<usize>,
+	pub row_indices: Vec<usize>,
+	pub values: Vec<K>,
+}
+
+// Sorts (major, minor, value) triplets, sums duplicates and builds the
+// compressed offsets shared by the CSR and CSC layouts
+
+fn compress<K: Traits>(
+	major_size: usize,
+	mut triplets: Vec<(usize, usize, K)>,
+) -> (Vec<usize>, Vec<usize>, Vec<K>) {
+	triplets.sort_by_key(|&(major, minor, _)| (major, minor));
+
+	let mut offsets = vec![0; major_size + 1];
+	let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+	let mut values: Vec<K> = Vec::with_capacity(triplets.len());
+	let mut last: Option<(usize, usize)> = None;
+
+	for (major, minor, value) in triplets {
+		if last == Some((major, minor)) {
+			let end = values.len() - 1;
+			values[end] += value;
+			continue;
+		}
+
+		offsets[major + 1] += 1;
+		indices.push(minor);
+		values.push(value);
+		last = Some((major, minor));
+	}
+
+	for i in 0..major_size {
+		offsets[i + 1] += offsets[i];
+	}
+
+	return (offsets, indices, values);
+}
+
+impl<K: Traits> CooMatrix<K> {
+	pub fn new(rows: usize, cols: usize) -> Self {
+		Self {
+			rows,
+			cols,
+			entries: Vec::new(),
+		}
+	}
+
+	pub fn push(&mut self, row: usize, col: usize, value: K) {
+		if row >= self.rows || col >= self.cols {
+			panic!("Error: Entry is out of the matrix bounds");
+		}
+
+		self.entries.push((row, col, value));
+	}
+
+	pub fn nnz(&self) -> usize {
+		return self.entries.len();
+	}
+
+	pub fn to_csr(&self) -> CsrMatrix<K> {
+		let (row_offsets, col_indices, values) = compress(self.rows, self.entries.clone());
+
+		CsrMatrix {
+			rows: self.rows,
+			cols: self.cols,
+			row_offsets,
+			col_indices,
+			values,
+		}
+	}
+
+	pub fn to_csc(&self) -> CscMatrix<K> {
+		let transposed = self.entries.iter().map(|&(i, j, v)| (j, i, v)).collect();
+		let (col_offsets, row_indices, values) = compress(self.cols, transposed);
+
+		CscMatrix {
+			rows: self.rows,
+			cols: self.cols,
+			col_offsets,
+			row_indices,
+			values,
+		}
+	}
+}
+
+impl<K: Traits> CsrMatrix<K> {
+	pub fn from_dense<const M: usize, const N: usize>(matrix: &Matrix<K, M, N>) -> Self {
+		let mut coo = CooMatrix::new(M, N);
+
+		for i in 0..M {
+			for j in 0..N {
+				if matrix.data[i][j] != K::default() {
+					coo.push(i, j, matrix.data[i][j]);
+				}
+			}
+		}
+
+		return coo.to_csr();
+	}
+
+	pub fn to_dense<const M: usize, const N: usize>(&self) -> Matrix<K, M, N> {
+		if self.rows != M || self.cols != N {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		let mut result: Matrix<K, M, N> = Matrix::zeros();
+
+		for i in 0..M {
+			for k in self.row_offsets[i]..self.row_offsets[i + 1] {
+				result.data[i][self.col_indices[k]] += self.values[k];
+			}
+		}
+
+		return result;
+	}
+
+	pub fn to_coo(&self) -> CooMatrix<K> {
+		let mut result = CooMatrix::new(self.rows, self.cols);
+
+		for i in 0..self.rows {
+			for k in self.row_offsets[i]..self.row_offsets[i + 1] {
+				result.push(i, self.col_indices[k], self.values[k]);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn to_csc(&self) -> CscMatrix<K> {
+		return self.to_coo().to_csc();
+	}
+
+	pub fn nnz(&self) -> usize {
+		return self.values.len();
+	}
+
+	pub fn get(&self, row: usize, col: usize) -> K {
+		if row >= self.rows || col >= self.cols {
+			panic!("Error: Entry is out of the matrix bounds");
+		}
+
+		let start = self.row_offsets[row];
+		let end = self.row_offsets[row + 1];
+
+		match self.col_indices[start..end].binary_search(&col) {
+			Ok(k) => self.values[start + k],
+			Err(_) => K::default(),
+		}
+	}
+
+	pub fn transpose(&self) -> CsrMatrix<K> {
+		let csc = self.to_csc();
+
+		// The CSC arrays of A are exactly the CSR arrays of A^T
+
+		CsrMatrix {
+			rows: self.cols,
+			cols: self.rows,
+			row_offsets: csc.col_offsets,
+			col_indices: csc.row_indices,
+			values: csc.values,
+		}
+	}
+
+	pub fn mul_slice(&self, x: &[K]) -> Vec<K> {
+		if x.len() != self.cols {
+			panic!("Error: Matrix and vector sizes are incompatible");
+		}
+
+		let mut result = vec![K::default(); self.rows];
+
+		for i in 0..self.rows {
+			for k in self.row_offsets[i]..self.row_offsets[i + 1] {
+				result[i] = self.values[k].mul_add(x[self.col_indices[k]], result[i]);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn mul_vec<const M: usize, const N: usize>(&self, vec: &Vector<K, N>) -> Vector<K, M> {
+		if self.rows != M {
+			panic!("Error: Matrix and vector sizes are incompatible");
+		}
+
+		let mut result = Vector::new([K::default(); M]);
+		result.data.copy_from_slice(&self.mul_slice(&vec.data));
+
+		return result;
+	}
+
+	// Sizes are only known at runtime, so stacking checks them here
+
+	pub fn hstack(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+		if self.rows != other.rows {
+			panic!("Error: hstack needs the same number of rows");
+		}
+
+		let mut result = CooMatrix::new(self.rows, self.cols + other.cols);
+		result.entries = self.to_coo().entries;
+		result.entries.extend(
+			other
+				.to_coo()
+				.entries
+				.into_iter()
+				.map(|(i, j, v)| (i, j + self.cols, v)),
+		);
+
+		return result.to_csr();
+	}
+
+	pub fn vstack(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+		if self.cols != other.cols {
+			panic!("Error: vstack needs the same number of columns");
+		}
+
+		let mut result = CooMatrix::new(self.rows + other.rows, self.cols);
+		result.entries = self.to_coo().entries;
+		result.entries.extend(
+			other
+				.to_coo()
+				.entries
+				.into_iter()
+				.map(|(i, j, v)| (i + self.rows, j, v)),
+		);
+
+		return result.to_csr();
+	}
+
+	pub fn block_diag(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+		let mut result = CooMatrix::new(self.rows + other.rows, self.cols + other.cols);
+		result.entries = self.to_coo().entries;
+		result.entries.extend(
+			other
+				.to_coo()
+				.entries
+				.into_iter()
+				.map(|(i, j, v)| (i + self.rows, j + self.cols, v)),
+		);
+
+		return result.to_csr();
+	}
+
+	pub fn kronecker(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+		let mut result = CooMatrix::new(self.rows * other.rows, self.cols * other.cols);
+		let inner = other.to_coo().entries;
+
+		for (i, j, a) in self.to_coo().entries {
+			for &(k, l, b) in &inner {
+				result.push(i * other.rows + k, j * other.cols + l, a * b);
+			}
+		}
+
+		return result.to_csr();
+	}
+
+	pub fn hsplit(&self, at: usize) -> (CsrMatrix<K>, CsrMatrix<K>) {
+		if at > self.cols {
+			panic!("Error: Split index is out of the matrix bounds");
+		}
+
+		let mut left = CooMatrix::new(self.rows, at);
+		let mut right = CooMatrix::new(self.rows, self.cols - at);
+
+		for (i, j, v) in self.to_coo().entries {
+			if j < at {
+				left.push(i, j, v);
+			} else {
+				right.push(i, j - at, v);
+			}
+		}
+
+		return (left.to_csr(), right.to_csr());
+	}
+
+	pub fn vsplit(&self, at: usize) -> (CsrMatrix<K>, CsrMatrix<K>) {
+		if at > self.rows {
+			panic!("Error: Split index is out of the matrix bounds");
+		}
+
+		let mut top = CooMatrix::new(at, self.cols);
+		let mut bottom = CooMatrix::new(self.rows - at, self.cols);
+
+		for (i, j, v) in self.to_coo().entries {
+			if i < at {
+				top.push(i, j, v);
+			} else {
+				bottom.push(i - at, j, v);
+			}
+		}
+
+		return (top.to_csr(), bottom.to_csr());
+	}
+
+	// Conjugate gradient, the matrix has to be symmetric positive definite
+
+	pub fn solve(&self, b: &[K], tolerance: f32, max_iterations: usize) -> Option<Vec<K>> {
+		let options = SolverOptions {
+			tolerance,
+			max_iterations,
+			..SolverOptions::default()
+		};
+		let result = solver::conjugate_gradient(self, b, &options, None);
+
+		if !result.converged {
+			return None;
+		}
+
+		return Some(result.solution);
+	}
+}
+
+impl<K: Traits> CscMatrix<K> {
+	pub fn from_dense<const M: usize, const N: usize>(matrix: &Matrix<K, M, N>) -> Self {
+		return CsrMatrix::from_dense(matrix).to_csc();
+	}
+
+	pub fn to_dense<const M: usize, const N: usize>(&self) -> Matrix<K, M, N> {
+		return self.to_csr().to_dense();
+	}
+
+	pub fn to_coo(&self) -> CooMatrix<K> {
+		let mut result = CooMatrix::new(self.rows, self.cols);
+
+		for j in 0..self.cols {
+			for k in self.col_offsets[j]..self.col_offsets[j + 1] {
+				result.push(self.row_indices[k], j, self.values[k]);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn to_csr(&self) -> CsrMatrix<K> {
+		return self.to_coo().to_csr();
+	}
+
+	pub fn nnz(&self) -> usize {
+		return self.values.len();
+	}
+
+	pub fn get(&self, row: usize, col: usize) -> K {
+		if row >= self.rows || col >= self.cols {
+			panic!("Error: Entry is out of the matrix bounds");
+		}
+
+		let start = self.col_offsets[col];
+		let end = self.col_offsets[col + 1];
+
+		match self.row_indices[start..end].binary_search(&row) {
+			Ok(k) => self.values[start + k],
+			Err(_) => K::default(),
+		}
+	}
+
+	pub fn transpose(&self) -> CscMatrix<K> {
+		return self.to_csr().transpose().to_csc();
+	}
+
+	pub fn mul_slice(&self, x: &[K]) -> Vec<K> {
+		if x.len() != self.cols {
+			panic!("Error: Matrix and vector sizes are incompatible");
+		}
+
+		let mut result = vec![K::default(); self.rows];
+
+		for j in 0..self.cols {
+			for k in self.col_offsets[j]..self.col_offsets[j + 1] {
+				let i = self.row_indices[k];
+				result[i] = self.values[k].mul_add(x[j], result[i]);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn mul_vec<const M: usize, const N: usize>(&self, vec: &Vector<K, N>) -> Vector<K, M> {
+		if self.rows != M {
+			panic!("Error: Matrix and vector sizes are incompatible");
+		}
+
+		let mut result = Vector::new([K::default(); M]);
+		result.data.copy_from_slice(&self.mul_slice(&vec.data));
+
+		return result;
+	}
+
+	pub fn solve(&self, b: &[K], tolerance: f32, max_iterations: usize) -> Option<Vec<K>> {
+		return self.to_csr().solve(b, tolerance, max_iterations);
+	}
+}
+
+impl<K: Traits> Add for CsrMatrix<K> {
+	type Output = Self;
+
+	fn add(self, v: Self) -> Self::Output {
+		if self.rows != v.rows || self.cols != v.cols {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		let mut coo = self.to_coo();
+		coo.entries.extend(v.to_coo().entries);
+
+		return coo.to_csr();
+	}
+}
+
+impl<K: Traits> Add for CscMatrix<K> {
+	type Output = Self;
+
+	fn add(self, v: Self) -> Self::Output {
+		if self.rows != v.rows || self.cols != v.cols {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		let mut coo = self.to_coo();
+		coo.entries.extend(v.to_coo().entries);
+
+		return coo.to_csc();
+	}
+}
@@ -6,16 +6,40 @@
 /*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
 /*                                                +#+#+#+#+#+   +#+           */
 /*   Created: 2026/01/25 15:47:32 by ggalon            #+#    #+#             */
-/*   Updated: 2026/01/25 15:47:33 by ggalon           ###   ########.fr       */
+/*   Updated: 2026/10/19 04:12:47 by ggalon           ###   ########.fr       */
 /*                                                                            */
 /* ************************************************************************** */
 
+#[allow(dead_code)]
+#[path = "../benches/common/mod.rs"]
+mod common;
+
+use common::fill;
+
+use crate::binary::{BinaryError, View};
+use crate::blas::{self, Diag, Side, Transpose, Uplo};
+use crate::calculator::{Calculator, ErrorKind, Value};
+use crate::checksum::crc32;
+use crate::elimination::{self, EliminationRecord, Form, RowOperation, Solution, Trace};
+use crate::file::{CsvOptions, FileError};
+use crate::format::{FormatOptions, Style};
+use crate::gemm::{gemm_blocked, gemm_naive};
+use crate::interpolation::{
+	bezier, catmull_rom, hermite, inverse_lerp, lerp_clamped, lerp_smooth, smoothstep,
+};
 use crate::lerp;
 use crate::matrix::Matrix;
+use crate::npy::{read_npy, read_npy_with, read_npz, write_npz, Dtype, NpyError, NpyOptions};
+use crate::parse::{parse_rows, ParseErrorKind};
+use crate::simd::Kernels;
+use crate::solver::{
+	conjugate_gradient, gauss_seidel, gmres, jacobi, JacobiPreconditioner, SolverOptions,
+};
+use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+use crate::traits::MulAdd;
 use crate::vector::Vector;
 
 #[cfg(test)]
-
 fn assert_approx_eq(a: f32, b: f32) {
 	assert!(
 		(a - b).abs() < 1e-6,
@@ -162,6 +186,78 @@ fn test_vector_lerp() {
 	}
 }
 
+#[test]
+fn test_interpolation_lerp_unclamped() {
+	assert_approx_eq(lerp(0., 10., 1.5), 15.0);
+	assert_approx_eq(lerp(0., 10., -0.5), -5.0);
+	assert_approx_eq(lerp_clamped(0., 10., 1.5), 10.0);
+	assert_approx_eq(lerp_clamped(0., 10., -0.5), 0.0);
+	assert_approx_eq(lerp_clamped(0., 10., 0.25), 2.5);
+}
+
+#[test]
+fn test_interpolation_inverse_lerp_smoothstep() {
+	assert_approx_eq(inverse_lerp(10., 20., 15.), 0.5);
+	assert_approx_eq(inverse_lerp(10., 20., 30.), 2.0);
+	assert!(inverse_lerp(1., 1., 1.).is_nan());
+
+	assert_approx_eq(smoothstep(0., 1., -1.), 0.0);
+	assert_approx_eq(smoothstep(0., 1., 0.5), 0.5);
+	assert_approx_eq(smoothstep(0., 1., 2.), 1.0);
+	assert_approx_eq(smoothstep(0., 2., 0.5), 0.15625);
+	assert_approx_eq(lerp_smooth(0., 8., 0.25), 1.25);
+}
+
+#[test]
+fn test_interpolation_splines() {
+	assert_approx_eq(hermite(0., 1., 1., 1., 0.5), 0.5);
+	assert_approx_eq(hermite(0., 0., 1., 0., 0.5), 0.5);
+	assert_approx_eq(hermite(2., 5., 7., -3., 0.), 2.0);
+	assert_approx_eq(hermite(2., 5., 7., -3., 1.), 7.0);
+
+	assert_approx_eq(catmull_rom(0., 1., 2., 3., 0.), 1.0);
+	assert_approx_eq(catmull_rom(0., 1., 2., 3., 1.), 2.0);
+	assert_approx_eq(catmull_rom(0., 1., 2., 3., 0.5), 1.5);
+
+	let p = catmull_rom(
+		Vector::new([0., 0.]),
+		Vector::new([1., 1.]),
+		Vector::new([2., 0.]),
+		Vector::new([3., 1.]),
+		0.5,
+	);
+	assert_approx_eq(p.data[0], 1.5);
+	assert_approx_eq(p.data[1], 0.5);
+}
+
+#[test]
+fn test_interpolation_bezier() {
+	assert_approx_eq(bezier([4.], 0.7), 4.0);
+	assert_approx_eq(bezier([0., 10.], 0.3), 3.0);
+	assert_approx_eq(bezier([0., 1., 0.], 0.5), 0.5);
+	assert_approx_eq(bezier([0., 0., 1., 1.], 0.5), 0.5);
+
+	let p = bezier(
+		[
+			Vector::new([0., 0.]),
+			Vector::new([1., 2.]),
+			Vector::new([2., 0.]),
+		],
+		0.5,
+	);
+	assert_approx_eq(p.data[0], 1.0);
+	assert_approx_eq(p.data[1], 1.0);
+
+	let m = bezier(
+		vec![
+			Matrix::new([[0., 0.], [0., 0.]]),
+			Matrix::new([[4., 8.], [0., -4.]]),
+		],
+		0.25,
+	);
+	assert_eq!(m.data, [[1., 2.], [0., -1.]]);
+}
+
 #[test]
 fn test_matrix_addition() {
 	let mut m1 = Matrix::new([[1., 2.], [3., 4.]]);
@@ -243,8 +339,37 @@ fn test_matrix_transpose() {
 
 #[test]
 fn test_matrix_row_echelon() {
-	let u = Matrix::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+	let u = Matrix::new([[1., 2.], [3., 4.]]);
 	let echelon = u.row_echelon();
+	let expected = Matrix::new([[3., 4.], [0., 2. / 3.]]);
+	for i in 0..2 {
+		for j in 0..2 {
+			assert_approx_eq(echelon.data[i][j], expected.data[i][j]);
+		}
+	}
+	assert!(echelon.is_upper_triangular(0.));
+
+	let u = Matrix::new([[2., 1., -1.], [-3., -1., 2.], [-2., 1., 2.]]);
+	let (echelon, record) = u.row_echelon_with_record();
+	assert!(echelon.is_upper_triangular(0.));
+	assert_eq!(record.rank(), 3);
+	assert_eq!(record.pivots, vec![(0, 0), (1, 1), (2, 2)]);
+	assert_eq!(record.swaps(), vec![(0, 1), (1, 2)]);
+	assert_eq!(record.multipliers().len(), 3);
+	assert_approx_eq(record.multipliers()[0].2, -2. / 3.);
+	assert_approx_eq(echelon.determinant().abs(), u.determinant().abs());
+
+	let u = Matrix::new([[1., 2., 0., 1.], [2., 4., 1., 4.], [4., 8., 2., 8.]]);
+	let (echelon, record) = u.reduced_row_echelon_with_record();
+	assert_eq!(record.pivots, vec![(0, 0), (1, 2)]);
+	assert_eq!(echelon.data[2], [0., 0., 0., 0.]);
+	assert_eq!(u.row_echelon().data[2], [0., 0., 0., 0.]);
+}
+
+#[test]
+fn test_matrix_reduced_row_echelon() {
+	let u = Matrix::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+	let echelon = u.reduced_row_echelon();
 	let expected = Matrix::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
 	for i in 0..3 {
 		for j in 0..3 {
@@ -253,7 +378,7 @@ fn test_matrix_row_echelon() {
 	}
 
 	let u = Matrix::new([[1., 2.], [3., 4.]]);
-	let echelon = u.row_echelon();
+	let echelon = u.reduced_row_echelon();
 	let expected = Matrix::new([[1., 0.], [0., 1.]]);
 	for i in 0..2 {
 		for j in 0..2 {
@@ -262,7 +387,7 @@ fn test_matrix_row_echelon() {
 	}
 
 	let u = Matrix::new([[1., 2.], [2., 4.]]);
-	let echelon = u.row_echelon();
+	let echelon = u.reduced_row_echelon();
 	let expected = Matrix::new([[1., 2.], [0., 0.]]);
 	for i in 0..2 {
 		for j in 0..2 {
@@ -275,7 +400,7 @@ fn test_matrix_row_echelon() {
 		[4., 2.5, 20., 4., -4.],
 		[8., 5., 1., 4., 17.],
 	]);
-	let echelon = u.row_echelon();
+	let echelon = u.reduced_row_echelon();
 	let expected = Matrix::new([
 		[1., 0.625, 0., 0., -12.166667],
 		[0., 0., 1., 0., -3.6666667],
@@ -353,3 +478,1733 @@ fn test_matrix_rank() {
 	let u = Matrix::new([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.], [21., 18., 7.]]);
 	assert_eq!(u.rank(), 3);
 }
+
+#[test]
+fn test_matrix_pow() {
+	let u = Matrix::new([[1., 1.], [0., 1.]]);
+	assert_eq!(u.pow(0).data, [[1., 0.], [0., 1.]]);
+	assert_eq!(u.pow(1).data, [[1., 1.], [0., 1.]]);
+	assert_eq!(u.pow(5).data, [[1., 5.], [0., 1.]]);
+	assert_eq!(u.pow(-3).data, [[1., -3.], [0., 1.]]);
+
+	let u = Matrix::new([[1., 1.], [1., 0.]]);
+	assert_eq!(u.pow(10).data, [[89., 55.], [55., 34.]]);
+
+	let u = Matrix::new([[4., 0.], [0., 9.]]);
+	let root = u.powf(0.5);
+	assert!((root.data[0][0] - 2.).abs() < 1e-4);
+	assert!((root.data[1][1] - 3.).abs() < 1e-4);
+	assert!(root.data[0][1].abs() < 1e-4);
+}
+
+#[test]
+fn test_matrix_exp() {
+	let u: Matrix<f32, 3, 3> = Matrix::new([[0.; 3]; 3]);
+	assert_eq!(u.exp().data, [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+
+	let u = Matrix::new([[1., 0.], [0., 2.]]);
+	let e = u.exp();
+	assert!((e.data[0][0] - 1f32.exp()).abs() < 1e-5);
+	assert!((e.data[1][1] - 2f32.exp()).abs() < 1e-5);
+	assert!(e.data[0][1].abs() < 1e-6);
+
+	let theta: f32 = 1.2;
+	let u = Matrix::new([[0., -theta], [theta, 0.]]);
+	let e = u.exp();
+	let expected = [[theta.cos(), -theta.sin()], [theta.sin(), theta.cos()]];
+	for i in 0..2 {
+		for j in 0..2 {
+			assert!((e.data[i][j] - expected[i][j]).abs() < 1e-5);
+		}
+	}
+
+	let u = Matrix::new([[0., 1.], [0., 0.]]);
+	let e = (u * 10.).exp();
+	assert!((e.data[0][1] - 10.).abs() < 1e-4);
+
+	for value in [f32::NAN, f32::INFINITY] {
+		let u = Matrix::new([[1., value], [0., 1.]]);
+		assert!(u.exp().data.as_flattened().iter().all(|x| x.is_nan()));
+	}
+}
+
+#[test]
+fn test_matrix_sqrt_log() {
+	let u = Matrix::new([[4., 1.], [1., 3.]]);
+	let root = u.sqrt();
+	let square = root.mul_mat(root.clone());
+	for i in 0..2 {
+		for j in 0..2 {
+			assert!((square.data[i][j] - u.data[i][j]).abs() < 1e-4);
+		}
+	}
+
+	let u = Matrix::new([[0.5, 0.2, 0.], [-0.1, 0.3, 0.4], [0., 0.2, -0.6]]);
+	let l = u.exp().log();
+	for i in 0..3 {
+		for j in 0..3 {
+			assert!((l.data[i][j] - u.data[i][j]).abs() < 1e-4);
+		}
+	}
+
+	let u = Matrix::new([[1., 0.], [0., 1.]]);
+	let l = u.log();
+	assert_eq!(l.data, [[0., 0.], [0., 0.]]);
+}
+
+#[test]
+fn test_sparse_coo_to_csr_csc() {
+	let mut coo = CooMatrix::new(3, 4);
+	coo.push(2, 3, 5.);
+	coo.push(0, 1, 2.);
+	coo.push(1, 0, -1.);
+	coo.push(0, 1, 3.);
+	coo.push(2, 0, 4.);
+
+	let csr = coo.to_csr();
+	assert_eq!(csr.row_offsets, vec![0, 1, 2, 4]);
+	assert_eq!(csr.col_indices, vec![1, 0, 0, 3]);
+	assert_eq!(csr.values, vec![5., -1., 4., 5.]);
+	assert_eq!(csr.get(0, 1), 5.);
+	assert_eq!(csr.get(1, 1), 0.);
+
+	let csc = coo.to_csc();
+	assert_eq!(csc.col_offsets, vec![0, 2, 3, 3, 4]);
+	assert_eq!(csc.row_indices, vec![1, 2, 0, 2]);
+	assert_eq!(csc.values, vec![-1., 4., 5., 5.]);
+	assert_eq!(csc.to_csr(), csr);
+
+	let dense: Matrix<f32, 3, 4> = csr.to_dense();
+	assert_eq!(
+		dense.data,
+		[[0., 5., 0., 0.], [-1., 0., 0., 0.], [4., 0., 0., 5.]]
+	);
+	assert_eq!(CsrMatrix::from_dense(&dense), csr);
+	assert_eq!(CscMatrix::from_dense(&dense), csc);
+}
+
+#[test]
+fn test_sparse_operations() {
+	let a = Matrix::new([[1., 0., 2.], [0., 0., 3.]]);
+	let b = Matrix::new([[0., 4., -2.], [5., 0., 0.]]);
+	let sa = CsrMatrix::from_dense(&a);
+	let sb = CsrMatrix::from_dense(&b);
+
+	let v = Vector::new([1., 2., 3.]);
+	let product: Vector<f32, 2> = sa.mul_vec(&v);
+	assert_eq!(product.data, [7., 9.]);
+	let product: Vector<f32, 2> = sa.to_csc().mul_vec(&v);
+	assert_eq!(product.data, [7., 9.]);
+
+	let sum: Matrix<f32, 2, 3> = (sa.clone() + sb.clone()).to_dense();
+	assert_eq!(sum.data, [[1., 4., 0.], [5., 0., 3.]]);
+	let sum: Matrix<f32, 2, 3> = (sa.to_csc() + sb.to_csc()).to_dense();
+	assert_eq!(sum.data, [[1., 4., 0.], [5., 0., 3.]]);
+
+	let t: Matrix<f32, 3, 2> = sa.transpose().to_dense();
+	assert_eq!(t.data, a.transpose().data);
+	let t: Matrix<f32, 3, 2> = sa.to_csc().transpose().to_dense();
+	assert_eq!(t.data, a.transpose().data);
+}
+
+#[test]
+fn test_sparse_solve() {
+	let mut coo = CooMatrix::new(5, 5);
+	for i in 0..5 {
+		coo.push(i, i, 2.);
+		if i > 0 {
+			coo.push(i, i - 1, -1.);
+			coo.push(i - 1, i, -1.);
+		}
+	}
+	let a = coo.to_csr();
+	let b = vec![1., 0., 0., 0., 1.];
+
+	let x = a.solve(&b, 1e-6, 100).unwrap();
+	for i in 0..5 {
+		assert_approx_eq(x[i], 1.0);
+	}
+	assert!(a.solve(&b, 1e-6, 1).is_none());
+}
+
+#[test]
+fn test_solver_conjugate_gradient() {
+	let a = Matrix::new([[4., 1., 0.], [1., 3., -1.], [0., -1., 2.]]);
+	let b = [5., 3., 1.];
+	let options = SolverOptions::default();
+
+	let result = conjugate_gradient(&a, &b, &options, None);
+	assert!(result.converged);
+	assert_eq!(result.residuals.len(), result.iterations + 1);
+	assert_approx_eq(result.residuals[0], 1.0);
+	for i in 0..3 {
+		assert!((result.solution[i] - 1.).abs() < 1e-5);
+	}
+
+	let preconditioner = JacobiPreconditioner::new(&a);
+	let result = conjugate_gradient(&a, &b, &options, Some(&preconditioner));
+	assert!(result.converged);
+	for i in 0..3 {
+		assert!((result.solution[i] - 1.).abs() < 1e-5);
+	}
+
+	let sparse = CsrMatrix::from_dense(&a);
+	let result = conjugate_gradient(&sparse, &b, &options, None);
+	assert!(result.converged);
+	for i in 0..3 {
+		assert!((result.solution[i] - 1.).abs() < 1e-5);
+	}
+}
+
+#[test]
+fn test_solver_gmres() {
+	let a = Matrix::new([[3., 2., 0.], [-1., 4., 1.], [2., 0., 5.]]);
+	let b = [5., 4., 7.];
+	let options = SolverOptions::default();
+
+	let result = gmres(&a, &b, &options, None);
+	assert!(result.converged);
+	assert!(result.iterations <= 3);
+	for i in 0..3 {
+		assert!((result.solution[i] - 1.).abs() < 1e-5);
+	}
+
+	let options = SolverOptions {
+		restart: 1,
+		..SolverOptions::default()
+	};
+	let preconditioner = JacobiPreconditioner::new(&a);
+	let result = gmres(
+		&CscMatrix::from_dense(&a),
+		&b,
+		&options,
+		Some(&preconditioner),
+	);
+	assert!(result.converged);
+	for i in 0..3 {
+		assert!((result.solution[i] - 1.).abs() < 1e-4);
+	}
+}
+
+#[test]
+fn test_solver_stationary_methods() {
+	let a = Matrix::new([[10., -1., 2.], [-1., 11., -1.], [2., -1., 10.]]);
+	let b = [11., 9., 11.];
+	let options = SolverOptions::default();
+
+	let result_jacobi = jacobi(&a, &b, &options);
+	let result_gauss_seidel = gauss_seidel(&CsrMatrix::from_dense(&a), &b, &options);
+	assert!(result_jacobi.converged);
+	assert!(result_gauss_seidel.converged);
+	assert!(result_gauss_seidel.iterations < result_jacobi.iterations);
+	for i in 0..3 {
+		assert!((result_jacobi.solution[i] - 1.).abs() < 1e-5);
+		assert!((result_gauss_seidel.solution[i] - 1.).abs() < 1e-5);
+	}
+
+	let options = SolverOptions {
+		max_iterations: 2,
+		..SolverOptions::default()
+	};
+	let result = jacobi(&a, &b, &options);
+	assert!(!result.converged);
+	assert_eq!(result.iterations, 2);
+	assert_eq!(result.residuals.len(), 3);
+}
+
+#[test]
+fn test_gemm_blocked_matches_naive() {
+	let (m, n, p) = (70, 131, 259);
+	let values = fill(m * n + n * p, 7);
+
+	let (a, b) = values.split_at(m * n);
+	let mut c_naive = vec![0.; m * p];
+	let mut c_blocked = vec![1.; m * p];
+	gemm_naive(a, b, &mut c_naive, m, n, p);
+	gemm_blocked(a, b, &mut c_blocked, m, n, p);
+
+	for i in 0..m * p {
+		assert_eq!(c_naive[i].to_bits(), c_blocked[i].to_bits());
+	}
+
+	let u = Matrix::new([[1., 2., 3.], [4., 5., 6.]]);
+	let v = Matrix::new([[7., 8.], [9., 10.], [11., 12.]]);
+	assert_eq!((u.clone() * v.clone()).data, [[58., 64.], [139., 154.]]);
+	assert_eq!(u.mul_mat(v).data, [[58., 64.], [139., 154.]]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_matches_serial() {
+	use crate::parallel;
+
+	let (m, n, p) = (67, 45, 93);
+	let values = fill(m * n + n * p, 11);
+
+	let (a, b) = values.split_at(m * n);
+	let mut c_serial = vec![0.; m * p];
+	let mut c_parallel = vec![0.; m * p];
+
+	let threshold = parallel::threshold();
+
+	parallel::set_threshold(0);
+	gemm_blocked(a, b, &mut c_serial, m, n, p);
+	parallel::gemm(a, b, &mut c_parallel, m, n, p);
+	assert_eq!(c_serial, c_parallel);
+
+	let mut rows = [[0f32; 8]; 8];
+	for i in 0..8 {
+		rows[i].copy_from_slice(&a[i * 8..(i + 1) * 8]);
+	}
+	let u = Matrix::new(rows);
+	let parallel_echelon = u.row_echelon();
+	let parallel_reduced = u.reduced_row_echelon();
+	let parallel_inverse = u.inverse();
+
+	parallel::set_threshold(usize::MAX);
+	let serial_echelon = u.row_echelon();
+	let serial_reduced = u.reduced_row_echelon();
+	let serial_inverse = u.inverse();
+
+	parallel::set_threshold(threshold);
+	assert_eq!(serial_echelon.data, parallel_echelon.data);
+	assert_eq!(serial_reduced.data, parallel_reduced.data);
+	assert_eq!(serial_inverse.data, parallel_inverse.data);
+}
+
+#[test]
+fn test_simd_kernels() {
+	let x: Vec<f32> = (0..37).map(|i| (i as f32 * 0.37).sin()).collect();
+	let y: Vec<f32> = (0..37).map(|i| (i as f32 * 0.11).cos()).collect();
+
+	let mut expected = 0f32;
+	for i in 0..37 {
+		expected = x[i].mul_add(y[i], expected);
+	}
+	assert!((f32::dot(&x, &y) - expected).abs() < 1e-5);
+
+	// Kernels::dot may reassociate, Vector::dot and blas::dot may not
+
+	let u: Vector<f32, 37> = Vector::new(x[..].try_into().unwrap());
+	let v: Vector<f32, 37> = Vector::new(y[..].try_into().unwrap());
+	assert_eq!(u.dot(v.clone()).to_bits(), expected.to_bits());
+	assert_eq!(blas::dot(&u, &v).to_bits(), expected.to_bits());
+
+	let mut result = y.clone();
+	f32::axpy(1.5, &x, &mut result);
+	for i in 0..37 {
+		assert_eq!(result[i].to_bits(), x[i].mul_add(1.5, y[i]).to_bits());
+	}
+
+	let x: Vec<f64> = (0..19).map(|i| i as f64 * 0.5).collect();
+	let y: Vec<f64> = (0..19).map(|i| 3. - i as f64).collect();
+	let mut expected = 0f64;
+	for i in 0..19 {
+		expected = x[i].mul_add(y[i], expected);
+	}
+	assert!((f64::dot(&x, &y) - expected).abs() < 1e-12);
+
+	let (m, n, p) = (5, 9, 11);
+	let a: Vec<f64> = (0..m * n).map(|i| (i as f64).sqrt()).collect();
+	let b: Vec<f64> = (0..n * p).map(|i| 1. / (i as f64 + 1.)).collect();
+	let mut c_naive = vec![0.; m * p];
+	let mut c_kernel = vec![0.; m * p];
+	gemm_naive(&a, &b, &mut c_naive, m, n, p);
+	f64::gemm(&a, &b, &mut c_kernel, m, n, p);
+	assert_eq!(c_naive, c_kernel);
+}
+
+#[test]
+fn test_blas_level_1() {
+	let x = Vector::new([1., -4., 2.]);
+	let mut y = Vector::new([3., 0., 1.]);
+	blas::axpy(2., &x, &mut y);
+	assert_eq!(y.data, [5., -8., 5.]);
+
+	blas::scal(0.5, &mut y);
+	assert_eq!(y.data, [2.5, -4., 2.5]);
+
+	assert_eq!(blas::dot(&x, &y), 23.5);
+	assert_eq!(blas::asum(&x), 7.);
+	assert_approx_eq(blas::nrm2(&x), 21f32.sqrt());
+	assert_eq!(blas::iamax(&x), 1);
+
+	let us = [Vector::new([1., 2.]), Vector::new([0., 10.])];
+	let coefs = [10., -2.];
+	let mut result = Vector::new([0., 0.]);
+	for i in 0..2 {
+		blas::axpy(coefs[i], &us[i], &mut result);
+	}
+	assert_eq!(result.data, Vector::linear_combination(&us, &coefs).data);
+}
+
+#[test]
+fn test_blas_level_2() {
+	let a = Matrix::new([[1., 2., 3.], [4., 5., 6.]]);
+	let mut y = Vector::new([1., 1.]);
+	blas::gemv(
+		Transpose::No,
+		2.,
+		&a,
+		&Vector::new([1., 0., -1.]),
+		3.,
+		&mut y,
+	);
+	assert_eq!(y.data, [-1., -1.]);
+
+	let mut y = Vector::new([f32::NAN; 3]);
+	blas::gemv(Transpose::Yes, 1., &a, &Vector::new([1., 1.]), 0., &mut y);
+	assert_eq!(y.data, [5., 7., 9.]);
+
+	let mut a = Matrix::new([[0., 0.], [1., 1.]]);
+	blas::ger(2., &Vector::new([1., 2.]), &Vector::new([3., 4.]), &mut a);
+	assert_eq!(a.data, [[6., 8.], [13., 17.]]);
+
+	let l = Matrix::new([[2., 0., 0.], [1., 1., 0.], [-1., 3., 4.]]);
+	let mut x = Vector::new([2., 3., 13.]);
+	blas::trsv(Uplo::Lower, Transpose::No, Diag::NonUnit, &l, &mut x);
+	assert_eq!(x.data, [1., 2., 2.]);
+
+	let mut x = Vector::new([1., 7., 8.]);
+	blas::trsv(Uplo::Lower, Transpose::Yes, Diag::NonUnit, &l, &mut x);
+	assert_eq!(x.data, [1., 1., 2.]);
+
+	let mut x = Vector::new([1., 3., 7.]);
+	blas::trsv(Uplo::Lower, Transpose::No, Diag::Unit, &l, &mut x);
+	assert_eq!(x.data, [1., 2., 2.]);
+}
+
+#[test]
+fn test_blas_level_3() {
+	let a = Matrix::new([[1., 2.], [3., 4.], [5., 6.]]);
+	let b = Matrix::new([[1., 0., 2.], [-1., 1., 0.]]);
+
+	let mut c = Matrix::new([[0.; 3]; 3]);
+	blas::gemm(Transpose::No, Transpose::No, 1., &a, &b, 0., &mut c);
+	assert_eq!(c.data, a.mul_mat(b.clone()).data);
+
+	let mut c = Matrix::new([[1., 1.], [1., 1.]]);
+	blas::gemm(Transpose::Yes, Transpose::Yes, 2., &a, &b, -1., &mut c);
+	assert_eq!(c.data, [[21., 3.], [27., 3.]]);
+
+	let mut c = Matrix::new([[0.; 2]; 2]);
+	blas::gemm(Transpose::Yes, Transpose::No, 1., &a, &a, 0., &mut c);
+	assert_eq!(c.data, [[35., 44.], [44., 56.]]);
+
+	let mut c = Matrix::new([[0., -1.], [-1., 0.]]);
+	blas::syrk(Uplo::Upper, Transpose::Yes, 1., &a, 0., &mut c);
+	assert_eq!(c.data, [[35., 44.], [-1., 56.]]);
+
+	let mut c = Matrix::new([[0.; 3]; 3]);
+	blas::syrk(Uplo::Lower, Transpose::No, 1., &a, 0., &mut c);
+	assert_eq!(c.data, [[5., 0., 0.], [11., 25., 0.], [17., 39., 61.]]);
+
+	let u = Matrix::new([[2., 1.], [0., 4.]]);
+	let mut x = Matrix::new([[4., 6.], [8., 12.]]);
+	blas::trsm(
+		Side::Left,
+		Uplo::Upper,
+		Transpose::No,
+		Diag::NonUnit,
+		1.,
+		&u,
+		&mut x,
+	);
+	assert_eq!(x.data, [[1., 1.5], [2., 3.]]);
+
+	let mut x = Matrix::new([[2., 5.], [4., 6.], [0., 8.]]);
+	blas::trsm(
+		Side::Right,
+		Uplo::Upper,
+		Transpose::No,
+		Diag::NonUnit,
+		2.,
+		&u,
+		&mut x,
+	);
+	assert_eq!(x.mul_mat(u.clone()).data, [[4., 10.], [8., 12.], [0., 16.]]);
+}
+
+#[test]
+fn test_matrix_views() {
+	let mut u = Matrix::new([[1., 2., 3., 4.], [5., 6., 7., 8.], [9., 10., 11., 12.]]);
+
+	assert_eq!(u.row(1), &[5., 6., 7., 8.]);
+	assert_eq!(u.column(2).iter().collect::<Vec<f32>>(), vec![3., 7., 11.]);
+	assert_eq!(u.iter_rows().count(), 3);
+	let sums: Vec<f32> = u.iter_columns().map(|c| c.iter().sum()).collect();
+	assert_eq!(sums, vec![15., 18., 21., 24.]);
+
+	let block = u.block(1, 1, 2, 3);
+	assert_eq!(block.rows(), 2);
+	assert_eq!(block.cols(), 3);
+	assert_eq!(block.get(1, 2), 12.);
+	assert_eq!(block.row(0), &[6., 7., 8.]);
+	assert_eq!(block.column(0).iter().collect::<Vec<f32>>(), vec![6., 10.]);
+	assert_eq!(block.block(1, 1, 1, 2).row(0), &[11., 12.]);
+	let copy: Matrix<f32, 2, 3> = block.to_matrix();
+	assert_eq!(copy.data, [[6., 7., 8.], [10., 11., 12.]]);
+
+	u.row_mut(0)[0] = -1.;
+	u.column_mut(3).set(2, 0.);
+	{
+		let mut block = u.block_mut(0, 1, 2, 2);
+		block.set(0, 0, 20.);
+		block.column_mut(1).iter_mut().for_each(|v| *v *= 10.);
+		block.block_mut(1, 0, 1, 1).fill(-6.);
+	}
+	assert_eq!(
+		u.data,
+		[[-1., 20., 30., 4.], [5., -6., 70., 8.], [9., 10., 11., 0.]]
+	);
+}
+
+#[test]
+fn test_matrix_submatrix_minor() {
+	let mut u = Matrix::new([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
+
+	let sub: Matrix<f32, 2, 2> = u.submatrix(1, 1);
+	assert_eq!(sub.data, [[5., 6.], [8., 9.]]);
+
+	let minor: Matrix<f32, 2, 2> = u.minor(1, 1);
+	assert_eq!(minor.data, [[1., 3.], [7., 9.]]);
+	let minor: Matrix<f32, 2, 2> = u.minor(0, 2);
+	assert_eq!(minor.data, [[4., 5.], [7., 8.]]);
+
+	u.set_block(0, 1, &Matrix::new([[0., 0.], [0., 0.]]));
+	assert_eq!(u.data, [[1., 0., 0.], [4., 0., 0.], [7., 8., 9.]]);
+
+	let source = Matrix::new([[1., 2.], [3., 4.]]);
+	u.block_mut(1, 0, 2, 2)
+		.assign_view(&source.block(0, 0, 2, 2));
+	assert_eq!(u.data, [[1., 0., 0.], [1., 2., 0.], [3., 4., 9.]]);
+}
+
+#[test]
+fn test_matrix_stacking() {
+	let a = Matrix::new([[1., 2.], [3., 4.]]);
+	let b = Matrix::new([[5.], [6.]]);
+	let c = Matrix::new([[7., 8.]]);
+
+	let h: Matrix<f32, 2, 3> = a.hstack(&b);
+	assert_eq!(h.data, [[1., 2., 5.], [3., 4., 6.]]);
+
+	let v: Matrix<f32, 3, 2> = a.vstack(&c);
+	assert_eq!(v.data, [[1., 2.], [3., 4.], [7., 8.]]);
+
+	let d: Matrix<f32, 3, 3> = a.block_diag(&Matrix::new([[9.]]));
+	assert_eq!(d.data, [[1., 2., 0.], [3., 4., 0.], [0., 0., 9.]]);
+
+	let blocks: Matrix<f32, 3, 3> = Matrix::from_blocks(&a, &b, &c, &Matrix::new([[9.]]));
+	assert_eq!(blocks.data, [[1., 2., 5.], [3., 4., 6.], [7., 8., 9.]]);
+
+	let (left, right): (Matrix<f32, 3, 2>, Matrix<f32, 3, 1>) = blocks.hsplit();
+	assert_eq!(left.data, [[1., 2.], [3., 4.], [7., 8.]]);
+	assert_eq!(right.data, [[5.], [6.], [9.]]);
+
+	let (top, bottom): (Matrix<f32, 1, 3>, Matrix<f32, 2, 3>) = blocks.vsplit();
+	assert_eq!(top.data, [[1., 2., 5.]]);
+	assert_eq!(bottom.data, [[3., 4., 6.], [7., 8., 9.]]);
+
+	let k: Matrix<f32, 4, 4> = a.kronecker(&Matrix::new([[0., 1.], [1., 0.]]));
+	assert_eq!(
+		k.data,
+		[
+			[0., 1., 0., 2.],
+			[1., 0., 2., 0.],
+			[0., 3., 0., 4.],
+			[3., 0., 4., 0.]
+		]
+	);
+}
+
+#[test]
+fn test_sparse_stacking() {
+	let a = Matrix::new([[1., 0.], [0., 2.]]);
+	let b = Matrix::new([[0., 3.], [4., 0.]]);
+	let sa = CsrMatrix::from_dense(&a);
+	let sb = CsrMatrix::from_dense(&b);
+
+	let h: Matrix<f32, 2, 4> = sa.hstack(&sb).to_dense();
+	assert_eq!(h.data, a.hstack::<2, 4>(&b).data);
+
+	let v: Matrix<f32, 4, 2> = sa.vstack(&sb).to_dense();
+	assert_eq!(v.data, a.vstack::<2, 4>(&b).data);
+
+	let d: Matrix<f32, 4, 4> = sa.block_diag(&sb).to_dense();
+	assert_eq!(d.data, a.block_diag::<2, 2, 4, 4>(&b).data);
+
+	let k: Matrix<f32, 4, 4> = sa.kronecker(&sb).to_dense();
+	assert_eq!(k.data, a.kronecker::<2, 2, 4, 4>(&b).data);
+
+	let (left, right) = sa.hstack(&sb).hsplit(2);
+	assert_eq!(left, sa);
+	assert_eq!(right, sb);
+
+	let (top, bottom) = sa.vstack(&sb).vsplit(2);
+	assert_eq!(top, sa);
+	assert_eq!(bottom, sb);
+}
+
+#[test]
+fn test_matrix_constructors() {
+	let z: Matrix<f32, 2, 3> = Matrix::zeros();
+	assert_eq!(z.data, [[0., 0., 0.], [0., 0., 0.]]);
+
+	let o: Matrix<f32, 2, 2> = Matrix::ones();
+	assert_eq!(o.data, [[1., 1.], [1., 1.]]);
+
+	let i: Matrix<f32, 3, 3> = Matrix::identity();
+	assert_eq!(i.data, [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+	let i: Matrix<f32, 2, 3> = Matrix::identity();
+	assert_eq!(i.data, [[1., 0., 0.], [0., 1., 0.]]);
+
+	let d = Matrix::from_diagonal(&Vector::new([2., -1.]));
+	assert_eq!(d.data, [[2., 0.], [0., -1.]]);
+
+	let f: Matrix<f32, 2, 3> = Matrix::from_fn(|i, j| (10 * i + j) as f32);
+	assert_eq!(f.data, [[0., 1., 2.], [10., 11., 12.]]);
+
+	let rows = [Vector::new([1., 2.]), Vector::new([3., 4.])];
+	assert_eq!(Matrix::from_rows(&rows).data, [[1., 2.], [3., 4.]]);
+	assert_eq!(Matrix::from_columns(&rows).data, [[1., 3.], [2., 4.]]);
+}
+
+#[test]
+fn test_matrix_special_constructors() {
+	let v: Matrix<f32, 3, 4> = Matrix::vandermonde(&Vector::new([1., 2., 3.]));
+	assert_eq!(
+		v.data,
+		[[1., 1., 1., 1.], [1., 2., 4., 8.], [1., 3., 9., 27.]]
+	);
+
+	let h: Matrix<f32, 3, 3> = Matrix::hilbert();
+	assert_approx_eq(h.data[0][0], 1.);
+	assert_approx_eq(h.data[1][2], 0.25);
+	assert_approx_eq(h.data[2][2], 0.2);
+
+	let t = Matrix::toeplitz(&Vector::new([1., 2., 3.]), &Vector::new([9., 4., 5., 6.]));
+	assert_eq!(
+		t.data,
+		[[1., 4., 5., 6.], [2., 1., 4., 5.], [3., 2., 1., 4.]]
+	);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_matrix_random() {
+	let a: Matrix<f32, 3, 3> = Matrix::random_seeded(42);
+	let b: Matrix<f32, 3, 3> = Matrix::random_seeded(42);
+	let c: Matrix<f32, 3, 3> = Matrix::random_seeded(43);
+
+	assert_eq!(a.data, b.data);
+	assert_ne!(a.data, c.data);
+	for row in a.data {
+		for value in row {
+			assert!((0. ..1.).contains(&value));
+		}
+	}
+}
+
+#[test]
+fn test_vector_combinators() {
+	let mut u = Vector::new([3., -1., 4., -1., 5.]);
+	let v = Vector::new([1., 1., 2., 2., 0.]);
+
+	assert_eq!(u.map(|x| x * 2.).data, [6., -2., 8., -2., 10.]);
+	assert_eq!(u.zip_map(&v, |a, b| a - b).data, [2., -2., 2., -3., 5.]);
+	assert_eq!(u.fold(0, |count, x| count + (x < 0.) as usize), 2);
+	assert_eq!(u.sum(), 10.);
+	assert_eq!(u.product(), 60.);
+	assert_eq!(u.min(), Some(-1.));
+	assert_eq!(u.argmin(), Some(1));
+	assert_eq!(u.max(), Some(5.));
+	assert_eq!(u.argmax(), Some(4));
+	assert!(u.any(|x| x > 4.));
+	assert!(!u.all(|x| x > 0.));
+
+	u.apply(|x| x.abs());
+	assert_eq!(u.data, [3., 1., 4., 1., 5.]);
+	assert!(u.all(|x| x > 0.));
+
+	let e: Vector<f32, 0> = Vector::new([]);
+	assert_eq!(e.max(), None);
+	assert_eq!(e.sum(), 0.);
+	assert_eq!(e.product(), 1.);
+}
+
+#[test]
+fn test_matrix_combinators() {
+	let mut u = Matrix::new([[1., -2.], [7., 0.5]]);
+	let v = Matrix::new([[2., 2.], [1., 1.]]);
+
+	assert_eq!(u.map(|x| x + 1.).data, [[2., -1.], [8., 1.5]]);
+	assert_eq!(u.zip_map(&v, |a, b| a * b).data, [[2., -4.], [7., 0.5]]);
+	assert_eq!(u.sum(), 6.5);
+	assert_eq!(u.product(), -7.);
+	assert_eq!(u.fold(f32::MIN, f32::max), 7.);
+	assert_eq!(u.argmin(), Some((0, 1)));
+	assert_eq!(u.argmax(), Some((1, 0)));
+	assert_eq!(u.min(), Some(-2.));
+	assert_eq!(u.max(), Some(7.));
+	assert!(u.any(|x| x == 0.5));
+	assert!(!u.all(|x| x >= 1.));
+
+	u.apply(|x| x * x);
+	assert_eq!(u.data, [[1., 4.], [49., 0.25]]);
+}
+
+#[test]
+fn test_matrix_structure_predicates() {
+	let s = Matrix::new([[2., 1., 0.], [1., 3., -1.], [0., -1., 4.]]);
+	assert!(s.is_symmetric(0.));
+	assert!(!s.is_skew_symmetric(0.));
+	assert!(!s.is_diagonal(0.));
+
+	let k = Matrix::new([[0., 2.], [-2., 0.]]);
+	assert!(k.is_skew_symmetric(0.));
+	assert!(!k.is_symmetric(0.));
+
+	let u = Matrix::new([[1., 2., 3.], [0., 4., 5.], [0., 0., 6.]]);
+	assert!(u.is_upper_triangular(0.));
+	assert!(!u.is_lower_triangular(0.));
+	assert!(u.transpose().is_lower_triangular(0.));
+
+	let r = Matrix::new([[1., 0., 0., 0.], [0., 2., 0., 0.]]);
+	assert!(r.is_diagonal(0.));
+	assert!(Matrix::new([[1., 1e-7], [0., 1.]]).is_identity(1e-6));
+	assert!(!Matrix::new([[1., 1e-3], [0., 1.]]).is_identity(1e-6));
+}
+
+#[test]
+fn test_matrix_numeric_predicates() {
+	let theta: f32 = 0.7;
+	let rotation = Matrix::new([[theta.cos(), -theta.sin()], [theta.sin(), theta.cos()]]);
+	assert!(rotation.is_orthogonal(1e-6));
+	assert!(!Matrix::new([[1., 1.], [0., 1.]]).is_orthogonal(1e-6));
+
+	let singular = Matrix::new([[1., 2.], [2., 4.]]);
+	assert!(singular.is_singular(1e-6));
+	assert!(!singular.is_invertible(1e-6));
+	assert!(Matrix::new([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]]).is_invertible(1e-6));
+
+	let mut big: Matrix<f32, 5, 5> = Matrix::identity();
+	assert!(big.is_invertible(1e-6));
+	big.data[4] = big.data[3];
+	assert!(big.is_singular(1e-6));
+
+	let small: Matrix<f32, 4, 4> = Matrix::identity() * 1e-3;
+	let small_big: Matrix<f32, 5, 5> = Matrix::identity() * 1e-3;
+	assert!(small.is_invertible(1e-8));
+	assert!(small_big.is_invertible(1e-8));
+	assert!(small.is_singular(1e-2));
+	assert!(small_big.is_singular(1e-2));
+
+	let spd = Matrix::new([[4., 1., 0.], [1., 3., -1.], [0., -1., 2.]]);
+	assert!(spd.is_positive_definite(1e-6));
+	assert!(!Matrix::new([[1., 2.], [2., 1.]]).is_positive_definite(1e-6));
+	assert!(!Matrix::new([[1., 2.], [0., 5.]]).is_positive_definite(1e-6));
+}
+
+#[test]
+fn test_matrix_fundamental_subspaces() {
+	let u = Matrix::new([[1., 2., 0., 1.], [2., 4., 1., 4.], [3., 6., 1., 5.]]);
+	assert_eq!(u.pivot_columns(), vec![0, 2]);
+	assert_eq!(u.rank(), 2);
+
+	let columns = u.column_space();
+	assert_eq!(columns.len(), 2);
+	assert_eq!(columns[0].data, [1., 2., 3.]);
+	assert_eq!(columns[1].data, [0., 1., 1.]);
+
+	let rows = u.row_space();
+	assert_eq!(rows.len(), 2);
+
+	let kernel = u.null_space();
+	assert_eq!(kernel.len(), 2);
+	for vector in &kernel {
+		let image = u.mul_vec(vector.clone());
+		for i in 0..3 {
+			assert_approx_eq(image.data[i][0], 0.);
+		}
+	}
+	assert_eq!(kernel[0].data, [-2., 1., 0., 0.]);
+
+	let left = u.left_null_space();
+	assert_eq!(left.len(), 1);
+	let image = u.transpose().mul_vec(left[0].clone());
+	for j in 0..4 {
+		assert_approx_eq(image.data[j][0], 0.);
+	}
+
+	let full: Matrix<f32, 2, 2> = Matrix::identity();
+	assert!(full.null_space().is_empty());
+	assert_eq!(full.pivot_columns(), vec![0, 1]);
+}
+
+#[test]
+fn test_matrix_solve() {
+	let a = Matrix::new([[2., 1., -1.], [-3., -1., 2.], [-2., 1., 2.]]);
+	let b = Vector::new([8., -11., -3.]);
+
+	match a.solve(&b) {
+		Solution::Unique(x) => {
+			assert_approx_eq(x.data[0], 2.);
+			assert_approx_eq(x.data[1], 3.);
+			assert_approx_eq(x.data[2], -1.);
+		}
+		other => panic!("unexpected solution {:?}", other),
+	}
+
+	let u = Matrix::new([[1., 2., 0., 1.], [2., 4., 1., 4.], [3., 6., 1., 5.]]);
+	let b = Vector::new([1., 3., 4.]);
+
+	match u.solve(&b) {
+		Solution::Infinite {
+			particular,
+			null_space,
+		} => {
+			let image = u.mul_vec(particular);
+			for i in 0..3 {
+				assert_approx_eq(image.data[i][0], b.data[i]);
+			}
+			assert_eq!(null_space.len(), 2);
+			for vector in &null_space {
+				let image = u.mul_vec(vector.clone());
+				for i in 0..3 {
+					assert_approx_eq(image.data[i][0], 0.);
+				}
+			}
+		}
+		other => panic!("unexpected solution {:?}", other),
+	}
+
+	let b = Vector::new([1., 3., 5.]);
+	assert_eq!(u.solve(&b), Solution::Inconsistent);
+
+	// A large right-hand side does not raise the pivot threshold of A
+
+	let identity: Matrix<f32, 2, 2> = Matrix::identity();
+	let b = Vector::new([1e9, 1e9]);
+	assert_eq!(identity.solve(&b), Solution::Unique(b.clone()));
+	let small = Matrix::new([[1e-3, 0.], [0., 1e-3]]);
+	let b = Vector::new([1e5, 1e5]);
+	match small.solve(&b) {
+		Solution::Unique(x) => {
+			assert!((x.data[0] / 1e8 - 1.).abs() < 1e-6);
+			assert!((x.data[1] / 1e8 - 1.).abs() < 1e-6);
+		}
+		other => panic!("unexpected solution {:?}", other),
+	}
+
+	let (solution, steps) = a.solve_with_steps(&Vector::new([8., -11., -3.]));
+	assert!(matches!(solution, Solution::Unique(_)));
+	assert_eq!(steps[0], RowOperation::Swap(0, 1));
+	assert!(steps
+		.iter()
+		.any(|op| matches!(op, RowOperation::AddMultiple { .. })));
+}
+
+#[test]
+fn test_elimination_trace() {
+	let u = Matrix::new([[2., 1.], [4., 3.]]);
+	let mut trace = Trace::new();
+	let reduced = u.reduced_row_echelon_traced(&mut trace);
+
+	assert_eq!(reduced.data, u.reduced_row_echelon().data);
+	assert_eq!(trace.initial, vec![vec![2., 1.], vec![4., 3.]]);
+	assert_eq!(trace.steps[0].operation, RowOperation::Swap(0, 1));
+	assert_eq!(trace.steps[0].matrix, vec![vec![4., 3.], vec![2., 1.]]);
+	assert_eq!(
+		trace.steps[1].operation,
+		RowOperation::Scale {
+			row: 0,
+			factor: 0.25
+		}
+	);
+	assert_eq!(trace.steps[1].matrix[0], vec![1., 0.75]);
+	assert_eq!(
+		trace.steps.last().unwrap().matrix,
+		vec![vec![1., 0.], vec![0., 1.]]
+	);
+
+	let text = trace.to_text();
+	assert!(text.starts_with("Initial matrix\n[ 2 1 ]\n[ 4 3 ]\n"));
+	assert!(text.contains("R1 <-> R2\n[ 4 3 ]\n[ 2 1 ]\n"));
+	assert!(text.contains("R2 <- R2 - 2 R1\n"));
+
+	let latex = trace.to_latex();
+	assert!(latex.starts_with("\\begin{bmatrix}\n2 & 1 \\\\\n4 & 3\n\\end{bmatrix}\n"));
+	assert!(latex.contains("\\xrightarrow{R_{1} \\leftrightarrow R_{2}}"));
+
+	let mut trace = Trace::new();
+	let inverse = u.inverse_traced(&mut trace);
+	assert_eq!(inverse.data, u.inverse().data);
+	assert_eq!(trace.split, Some(2));
+	assert!(trace.to_latex().starts_with("\\left[\\begin{array}{cc|cc}"));
+	assert!(trace.to_text().contains("[ 2 1 | 1 0 ]"));
+
+	let mut record = EliminationRecord::new();
+	u.row_echelon_traced(&mut record);
+	assert_eq!(record, u.row_echelon_with_record().1);
+
+	// Noise cleared by the tolerance shows up as its own step
+
+	let mut rows = vec![
+		vec![1., 2., 0., 1.],
+		vec![2., 4., 1., 4.],
+		vec![3., 6., 1., 5.],
+	];
+	let tolerance = elimination::tolerance(&rows);
+	let mut trace = Trace::new();
+	elimination::eliminate(&mut rows, 4, Form::Reduced, tolerance, Some(&mut trace));
+	assert_eq!(trace.steps.last().unwrap().matrix, rows);
+	assert!(trace
+		.steps
+		.iter()
+		.any(|step| matches!(step.operation, RowOperation::Clear { col: 3, .. })));
+	assert!(trace.to_text().contains("C4 <- 0 in R3\n"));
+}
+
+#[test]
+fn test_matrix_format() {
+	let u = Matrix::new([[1., -0.5], [0.75, 10.]]);
+
+	let latex = u.format_with(&FormatOptions::new(Style::Bmatrix));
+	assert_eq!(
+		latex,
+		"\\begin{bmatrix}\n1 & -0.5 \\\\\n0.75 & 10\n\\end{bmatrix}\n"
+	);
+
+	let options = FormatOptions {
+		style: Style::Pmatrix,
+		rational: true,
+		..FormatOptions::default()
+	};
+	assert_eq!(
+		u.format_with(&options),
+		"\\begin{pmatrix}\n1 & -\\frac{1}{2} \\\\\n\\frac{3}{4} & 10\n\\end{pmatrix}\n"
+	);
+
+	let markdown = u.format_with(&FormatOptions::new(Style::Markdown));
+	assert_eq!(
+		markdown,
+		"|   |   |\n|--:|--:|\n| 1 | -0.5 |\n| 0.75 | 10 |\n"
+	);
+
+	let options = FormatOptions {
+		style: Style::MathMl,
+		rational: true,
+		..FormatOptions::default()
+	};
+	let mathml = u.format_with(&options);
+	assert!(mathml.starts_with("<math><mrow><mo>[</mo><mtable><mtr><mtd><mn>1</mn></mtd>"));
+	assert!(
+		mathml.contains("<mtd><mrow><mo>-</mo><mfrac><mn>1</mn><mn>2</mn></mfrac></mrow></mtd>")
+	);
+
+	let options = FormatOptions {
+		precision: Some(2),
+		..FormatOptions::default()
+	};
+	assert_eq!(
+		u.format_with(&options),
+		"┌            ┐\n│ 1.00 -0.50 │\n│ 0.75 10.00 │\n└            ┘\n"
+	);
+	assert_eq!(format!("{:.2}", u), u.format_with(&options));
+
+	let third = Matrix::new([[1. / 3., 2. / 3.]]);
+	let options = FormatOptions {
+		rational: true,
+		..FormatOptions::default()
+	};
+	assert_eq!(
+		third.format_with(&options),
+		"┌         ┐\n│ 1/3 2/3 │\n└         ┘\n"
+	);
+
+	let v = Vector::new([1., 2.]);
+	assert_eq!(format!("{}", v), "┌   ┐\n│ 1 │\n│ 2 │\n└   ┘\n");
+
+	let mut buffer = Vec::new();
+	v.write_formatted_io(&mut buffer, &FormatOptions::new(Style::Bmatrix))
+		.unwrap();
+	assert_eq!(buffer, b"\\begin{bmatrix}\n1 \\\\\n2\n\\end{bmatrix}\n");
+}
+
+#[test]
+fn test_matrix_parse() {
+	let expected = Matrix::new([[1., 2.], [3., 4.]]);
+
+	let u: Matrix<f32, 2, 2> = "[[1, 2], [3, 4]]".parse().unwrap();
+	assert_eq!(u, expected);
+	let u: Matrix<f32, 2, 2> = "[1 2; 3 4]".parse().unwrap();
+	assert_eq!(u, expected);
+	let u: Matrix<f32, 2, 2> = "1 2\n3 4\n".parse().unwrap();
+	assert_eq!(u, expected);
+	let u: Matrix<f32, 2, 2> = "[\n  [1, 2],\n  [3, 4]\n]".parse().unwrap();
+	assert_eq!(u, expected);
+	let u: Matrix<f32, 2, 2> = "[1, -2.5e1\n 3, 4]".parse().unwrap();
+	assert_eq!(u.data[0][1], -25.);
+
+	let v: Vector<f32, 3> = "[1 2 3]".parse().unwrap();
+	assert_eq!(v.data, [1., 2., 3.]);
+	let v: Vector<f32, 3> = "[1; 2; 3]".parse().unwrap();
+	assert_eq!(v.data, [1., 2., 3.]);
+
+	let error = "[[1, 2], [3, 4, 5]]"
+		.parse::<Matrix<f32, 2, 2>>()
+		.unwrap_err();
+	assert_eq!(
+		error.kind,
+		ParseErrorKind::RaggedRow {
+			expected: 2,
+			found: 3
+		}
+	);
+	assert_eq!((error.line, error.column), (1, 10));
+
+	let error = "1 2\n3 x".parse::<Matrix<f32, 2, 2>>().unwrap_err();
+	assert_eq!(error.kind, ParseErrorKind::UnexpectedCharacter('x'));
+	assert_eq!((error.line, error.column), (2, 3));
+	assert_eq!(error.to_string(), "2:3: unexpected character 'x'");
+
+	let error = "[1 2; 3 4".parse::<Matrix<f32, 2, 2>>().unwrap_err();
+	assert_eq!(error.kind, ParseErrorKind::UnexpectedEnd);
+	assert_eq!((error.line, error.column), (1, 10));
+
+	let error = "[1 2.3.4]".parse::<Matrix<f32, 1, 2>>().unwrap_err();
+	assert_eq!(
+		error.kind,
+		ParseErrorKind::InvalidNumber(String::from("2.3.4"))
+	);
+	assert_eq!((error.line, error.column), (1, 4));
+
+	let error = "[1 2]".parse::<Matrix<f32, 2, 2>>().unwrap_err();
+	assert_eq!(
+		error.kind,
+		ParseErrorKind::Dimensions {
+			expected: (2, 2),
+			found: (1, 2)
+		}
+	);
+
+	let sparse: CsrMatrix<f32> = "[1 0 0; 0 0 2]".parse().unwrap();
+	assert_eq!((sparse.rows, sparse.cols, sparse.nnz()), (2, 3, 2));
+	assert_eq!(sparse.get(1, 2), 2.);
+	assert_eq!(
+		parse_rows::<f32>("1,2\n3,4").unwrap(),
+		vec![vec![1., 2.], vec![3., 4.]]
+	);
+}
+
+#[test]
+fn test_csv() {
+	let options = CsvOptions {
+		delimiter: ';',
+		has_header: true,
+	};
+	let input = "x;y;z\n1;2;3\n\n4; 5.5;-6\n";
+	let u: Matrix<f32, 2, 3> = Matrix::read_csv(input.as_bytes(), &options).unwrap();
+	assert_eq!(u, Matrix::new([[1., 2., 3.], [4., 5.5, -6.]]));
+
+	let mut output = Vec::new();
+	u.write_csv(&mut output, &CsvOptions::default()).unwrap();
+	assert_eq!(output, b"1,2,3\n4,5.5,-6\n");
+	let v: Matrix<f32, 2, 3> = Matrix::read_csv(&output[..], &CsvOptions::default()).unwrap();
+	assert_eq!(u, v);
+
+	let error = Matrix::<f32, 2, 2>::read_csv("1,2\n3\n".as_bytes(), &CsvOptions::default());
+	assert!(matches!(
+		error,
+		Err(FileError::RaggedRow {
+			line: 2,
+			expected: 2,
+			found: 1
+		})
+	));
+	let error = Matrix::<f32, 1, 2>::read_csv("1,a\n".as_bytes(), &CsvOptions::default());
+	assert!(matches!(
+		error,
+		Err(FileError::InvalidNumber { line: 1, .. })
+	));
+	let error = Matrix::<f32, 2, 2>::read_csv("1,2\n".as_bytes(), &CsvOptions::default());
+	assert!(matches!(error, Err(FileError::Dimensions { .. })));
+}
+
+#[test]
+fn test_matrix_market() {
+	let input = "%%MatrixMarket matrix coordinate real symmetric\n% comment\n3 3 3\n1 1 2.0\n2 1 -1\n3 3 4e0\n";
+	let csr: CsrMatrix<f32> = CsrMatrix::read_matrix_market(input.as_bytes()).unwrap();
+	let dense: Matrix<f32, 3, 3> = csr.to_dense();
+	assert_eq!(
+		dense,
+		Matrix::new([[2., -1., 0.], [-1., 0., 0.], [0., 0., 4.]])
+	);
+
+	let mut output = Vec::new();
+	csr.write_matrix_market(&mut output).unwrap();
+	let coo: CooMatrix<f32> = CooMatrix::read_matrix_market(&output[..]).unwrap();
+	assert_eq!(coo.to_csr(), csr);
+
+	let input = "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 2\n2 1\n";
+	let csc: CscMatrix<f32> = CscMatrix::read_matrix_market(input.as_bytes()).unwrap();
+	assert_eq!(csc.to_dense::<2, 2>(), Matrix::new([[0., 1.], [1., 0.]]));
+
+	let input = "%%MatrixMarket matrix array integer skew-symmetric\n3 3\n1\n2\n3\n";
+	let u: Matrix<f32, 3, 3> = Matrix::read_matrix_market(input.as_bytes()).unwrap();
+	assert_eq!(
+		u,
+		Matrix::new([[0., -1., -2.], [1., 0., -3.], [2., 3., 0.]])
+	);
+
+	let u = Matrix::new([[1., 2.], [3., 4.]]);
+	let mut output = Vec::new();
+	u.write_matrix_market(&mut output).unwrap();
+	assert_eq!(
+		output,
+		b"%%MatrixMarket matrix array real general\n2 2\n1\n3\n2\n4\n"
+	);
+	assert_eq!(Matrix::read_matrix_market(&output[..]).unwrap(), u);
+
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix coordinate complex general\n".as_bytes(),
+	);
+	assert!(matches!(error, Err(FileError::Unsupported(_))));
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1\n".as_bytes(),
+	);
+	assert!(matches!(
+		error,
+		Err(FileError::OutOfBounds {
+			line: 3,
+			row: 3,
+			col: 1
+		})
+	));
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1\n".as_bytes(),
+	);
+	assert!(matches!(
+		error,
+		Err(FileError::EntryCount {
+			expected: 2,
+			found: 1
+		})
+	));
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix array integer general\n1 1\n1.5\n".as_bytes(),
+	);
+	assert!(matches!(
+		error,
+		Err(FileError::InvalidNumber { line: 3, .. })
+	));
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix array real general\n200000 200000\n1\n".as_bytes(),
+	);
+	assert!(matches!(
+		error,
+		Err(FileError::TooLarge {
+			rows: 200000,
+			cols: 200000
+		})
+	));
+	let error = CooMatrix::<f32>::read_matrix_market(
+		"%%MatrixMarket matrix array real symmetric\n2 2\n1\n2\n3\n4\n".as_bytes(),
+	);
+	assert!(matches!(
+		error,
+		Err(FileError::EntryCount {
+			expected: 3,
+			found: 4
+		})
+	));
+	let error = CooMatrix::<f32>::read_matrix_market("matrix\n".as_bytes());
+	assert!(matches!(error, Err(FileError::Header { line: 1, .. })));
+}
+
+#[test]
+fn test_npy() {
+	let u = Matrix::new([[1., 2., 3.], [4., 5., 6.]]);
+
+	let mut bytes = Vec::new();
+	u.write_npy(&mut bytes, &NpyOptions::default()).unwrap();
+	assert_eq!(&bytes[..10], b"\x93NUMPY\x01\x00\x76\x00");
+	assert!(bytes[10..].starts_with(b"{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"));
+	assert_eq!(bytes.len(), 128 + 6 * 4);
+	assert_eq!(bytes[127], b'\n');
+	assert_eq!(Matrix::read_npy(&bytes[..]).unwrap(), u);
+
+	let options = NpyOptions {
+		dtype: Dtype::F8,
+		fortran_order: true,
+		..NpyOptions::default()
+	};
+	let mut bytes = Vec::new();
+	u.write_npy(&mut bytes, &options).unwrap();
+	assert_eq!(bytes.len(), 128 + 6 * 8);
+	assert_eq!(&bytes[136..144], &4f64.to_le_bytes());
+	assert_eq!(Matrix::read_npy(&bytes[..]).unwrap(), u);
+
+	let array = read_npy::<f32, _>(&bytes[..]).unwrap();
+	assert_eq!(array.shape, vec![2, 3]);
+	assert_eq!(array.data, vec![1., 2., 3., 4., 5., 6.]);
+
+	let v = Vector::new([1.5, -2.]);
+	let mut bytes = Vec::new();
+	v.write_npy(&mut bytes, &NpyOptions::default()).unwrap();
+	assert!(String::from_utf8_lossy(&bytes).contains("'shape': (2,)"));
+	assert_eq!(Vector::read_npy(&bytes[..]).unwrap(), v);
+
+	let mut header = b"\x93NUMPY\x01\x00\x46\x00".to_vec();
+	header.extend_from_slice(b"{'descr': '<i8', 'fortran_order': False, 'shape': (1,), }");
+	header.resize(80, b' ');
+	assert!(matches!(
+		read_npy::<f32, _>(&header[..]),
+		Err(NpyError::Unsupported(_))
+	));
+	assert!(matches!(
+		Matrix::<f32, 3, 2>::read_npy(&b"PK\x03\x04\x14\x00\x00\x00"[..]),
+		Err(NpyError::Magic)
+	));
+	assert!(matches!(
+		Matrix::<f32, 3, 2>::from_npy(&u.to_npy()),
+		Err(NpyError::Shape { .. })
+	));
+
+	// Laid out as np.save writes np.arange(6.).reshape(2, 3) stored '>f8'
+	// in Fortran order, and np.array([0.5, 0.1, 2.])
+
+	let fixture = include_bytes!("../tests/fixtures/fortran_be_f8.npy");
+	let array = read_npy::<f32, _>(&fixture[..]).unwrap();
+	assert_eq!(array.shape, vec![2, 3]);
+	assert_eq!(array.data, vec![0., 1., 2., 3., 4., 5.]);
+
+	let fixture = include_bytes!("../tests/fixtures/tenth_f8.npy");
+	let array = read_npy::<f32, _>(&fixture[..]).unwrap();
+	assert_eq!(array.data, vec![0.5, 0.1, 2.]);
+	let options = NpyOptions {
+		exact: true,
+		..NpyOptions::default()
+	};
+	assert!(matches!(
+		read_npy_with::<f32, _>(&fixture[..], &options),
+		Err(NpyError::Precision { index: 1, .. })
+	));
+
+	// Sizes from the header are not trusted
+
+	let mut header = b"\x93NUMPY\x01\x00\x76\x00".to_vec();
+	header.extend_from_slice(
+		b"{'descr': '<f4', 'fortran_order': False, 'shape': (1099511627776, 1099511627776), }",
+	);
+	header.resize(128, b' ');
+	assert!(matches!(
+		read_npy::<f32, _>(&header[..]),
+		Err(NpyError::Header(_))
+	));
+
+	let mut header = b"\x93NUMPY\x01\x00\x76\x00".to_vec();
+	header.extend_from_slice(
+		b"{'descr': '<f4', 'fortran_order': False, 'shape': (1099511627776,), }",
+	);
+	header.resize(128, b' ');
+	assert!(matches!(
+		read_npy::<f32, _>(&header[..]),
+		Err(NpyError::Io(_))
+	));
+}
+
+#[test]
+fn test_npz() {
+	let u = Matrix::new([[1., 2.], [3., 4.]]);
+	let v = Vector::new([5., 6., 7.]);
+
+	let mut bytes = Vec::new();
+	write_npz(
+		&mut bytes,
+		&[("u", &u.to_npy()), ("v", &v.to_npy())],
+		&NpyOptions::default(),
+	)
+	.unwrap();
+	assert_eq!(&bytes[..4], b"PK\x03\x04");
+
+	let arrays = read_npz::<f32, _>(&bytes[..]).unwrap();
+	assert_eq!(arrays.len(), 2);
+	assert_eq!(arrays[0].0, "u");
+	assert_eq!(Matrix::<f32, 2, 2>::from_npy(&arrays[0].1).unwrap(), u);
+	assert_eq!(arrays[1].0, "v");
+	assert_eq!(Vector::<f32, 3>::from_npy(&arrays[1].1).unwrap(), v);
+
+	// Marked as deflated, as numpy.savez_compressed writes it
+
+	let mut deflated = bytes.clone();
+	let central = (0..deflated.len())
+		.find(|&at| deflated[at..].starts_with(b"PK\x01\x02"))
+		.unwrap();
+	deflated[central + 10] = 8;
+	match read_npz::<f32, _>(&deflated[..]) {
+		Err(error @ NpyError::Unsupported(_)) => {
+			assert!(error.to_string().contains("deflated member 'u.npy'"))
+		}
+		_ => panic!("Error: deflated member accepted"),
+	}
+
+	bytes[170] ^= 1;
+	assert!(matches!(
+		read_npz::<f32, _>(&bytes[..]),
+		Err(NpyError::Checksum { .. })
+	));
+
+	assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+	let u = Matrix::new([[1., 2., 3.], [4., 5., 6.]]);
+	let json = serde_json::to_string(&u).unwrap();
+	assert_eq!(json, "[[1.0,2.0,3.0],[4.0,5.0,6.0]]");
+	assert_eq!(serde_json::from_str::<Matrix<f32, 2, 3>>(&json).unwrap(), u);
+
+	let v = Vector::new([1.5, -2.]);
+	let json = serde_json::to_string(&v).unwrap();
+	assert_eq!(json, "[1.5,-2.0]");
+	assert_eq!(serde_json::from_str::<Vector<f32, 2>>(&json).unwrap(), v);
+
+	let big: Matrix<f32, 40, 40> = Matrix::identity();
+	let json = serde_json::to_string(&big).unwrap();
+	assert_eq!(
+		serde_json::from_str::<Matrix<f32, 40, 40>>(&json).unwrap(),
+		big
+	);
+
+	let error = serde_json::from_str::<Matrix<f32, 2, 2>>("[[1, 2], [3]]").unwrap_err();
+	assert!(error.to_string().contains("invalid length 1"));
+	let error = serde_json::from_str::<Matrix<f32, 2, 2>>("[[1, 2], [3, 4], [5, 6]]").unwrap_err();
+	assert!(error.to_string().contains("invalid length 3"));
+	assert!(serde_json::from_str::<Vector<f32, 3>>("[1, 2]").is_err());
+	assert!(serde_json::from_str::<Vector<f32, 1>>("1").is_err());
+}
+
+#[test]
+fn test_binary() {
+	let u = Matrix::new([[1., 2., 3.], [4., 5., 6.]]);
+	let bytes = u.to_bytes();
+
+	assert_eq!(&bytes[..8], b"MTRX\x01\x01\x01\x00");
+	assert_eq!(&bytes[8..16], &[2, 0, 0, 0, 3, 0, 0, 0]);
+	assert_eq!(&bytes[16..20], &1f32.to_le_bytes());
+	assert_eq!(bytes.len(), 16 + 6 * 4 + 4);
+	assert_eq!(Matrix::from_bytes(&bytes).unwrap(), u);
+
+	let v = Vector::new([7., 8.]);
+	let mut stream = Vec::new();
+	u.write_binary(&mut stream).unwrap();
+	v.write_binary(&mut stream).unwrap();
+
+	let first = View::<f32>::parse(&stream).unwrap();
+	assert!(first.is_matrix());
+	assert_eq!(first.get(1, 2), 6.);
+	assert_eq!(
+		first.iter().collect::<Vec<f32>>(),
+		vec![1., 2., 3., 4., 5., 6.]
+	);
+	let second = View::<f32>::parse(&stream[first.encoded_len()..]).unwrap();
+	assert_eq!(second.to_vector::<2>().unwrap(), v);
+	assert_eq!(first.to_matrix::<2, 3>().unwrap(), u);
+
+	let mut aligned = vec![0f32; bytes.len().div_ceil(4)];
+	let storage = unsafe { aligned.align_to_mut::<u8>().1 };
+	storage[..bytes.len()].copy_from_slice(&bytes);
+	let view = View::<f32>::parse(&storage[..bytes.len()]).unwrap();
+	assert_eq!(view.as_slice(), Some(&[1., 2., 3., 4., 5., 6.][..]));
+
+	let mut corrupted = bytes.clone();
+	corrupted[20] ^= 1;
+	assert!(matches!(
+		Matrix::<f32, 2, 3>::from_bytes(&corrupted),
+		Err(BinaryError::Checksum { .. })
+	));
+	assert_eq!(
+		Matrix::<f32, 2, 3>::from_bytes(&bytes[..30]),
+		Err(BinaryError::Truncated {
+			expected: 44,
+			found: 30
+		})
+	);
+
+	let mut huge = bytes[..24].to_vec();
+	huge[8..16].fill(0xFF);
+	assert_eq!(
+		View::<f32>::parse(&huge).unwrap_err(),
+		BinaryError::SizeOverflow {
+			rows: u32::MAX as usize,
+			cols: u32::MAX as usize
+		}
+	);
+
+	// Header bytes outside the defined values are rejected before the
+	// checksum
+
+	let mut record = v.to_bytes();
+	record[6] = 2;
+	assert_eq!(
+		View::<f32>::parse(&record).unwrap_err(),
+		BinaryError::UnknownKind(2)
+	);
+	let mut record = v.to_bytes();
+	record[7] = 1;
+	assert_eq!(
+		View::<f32>::parse(&record).unwrap_err(),
+		BinaryError::Reserved(1)
+	);
+	let mut record = v.to_bytes();
+	record[8..16].copy_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+	assert_eq!(
+		View::<f32>::parse(&record).unwrap_err(),
+		BinaryError::Dimensions {
+			expected: (1, 1),
+			found: (1, 2)
+		}
+	);
+	assert_eq!(
+		Matrix::<f32, 3, 2>::from_bytes(&bytes),
+		Err(BinaryError::Dimensions {
+			expected: (3, 2),
+			found: (2, 3)
+		})
+	);
+	assert_eq!(
+		Vector::<f32, 6>::from_bytes(&bytes),
+		Err(BinaryError::Kind {
+			expected: 0,
+			found: 1
+		})
+	);
+	assert_eq!(
+		View::<f64>::parse(&bytes).unwrap_err(),
+		BinaryError::Scalar {
+			expected: 2,
+			found: 1
+		}
+	);
+	assert_eq!(
+		Matrix::<f32, 2, 3>::from_bytes(b"NOPE0000000000000000"),
+		Err(BinaryError::Magic)
+	);
+}
+
+#[test]
+fn test_calculator() {
+	let mut calculator = Calculator::new();
+
+	calculator.eval("a = [2 0; 0 4]").unwrap();
+	assert_eq!(calculator.eval("det(a)").unwrap(), Some(Value::Scalar(8.)));
+	assert_eq!(
+		calculator.eval("inv(a) * a").unwrap(),
+		Some(Value::Matrix {
+			rows: 2,
+			cols: 2,
+			data: vec![1., 0., 0., 1.]
+		})
+	);
+	assert_eq!(
+		calculator.eval("a * [1 1] - [1 1]").unwrap(),
+		Some(Value::Vector(vec![1., 3.]))
+	);
+	assert_eq!(
+		calculator.eval("trace(a) / 2 + -1").unwrap(),
+		Some(Value::Scalar(2.))
+	);
+	assert_eq!(calculator.get("ans"), Some(&Value::Scalar(2.)));
+	assert_eq!(
+		calculator.eval("rank([1 2; 2 4])").unwrap(),
+		Some(Value::Scalar(1.))
+	);
+	assert_eq!(
+		calculator.eval("rref([1 2 3; 4 5 6])").unwrap(),
+		Some(Value::Matrix {
+			rows: 2,
+			cols: 3,
+			data: vec![1., 0., -1., 0., 1., 2.]
+		})
+	);
+	assert_eq!(
+		calculator
+			.eval("transpose([[1, 2, 3], [4, 5, 6]])")
+			.unwrap(),
+		Some(Value::Matrix {
+			rows: 3,
+			cols: 2,
+			data: vec![1., 4., 2., 5., 3., 6.]
+		})
+	);
+	assert_eq!(
+		calculator.eval("dot([1 2 3], [4 5 6])").unwrap(),
+		Some(Value::Scalar(32.))
+	);
+	assert_eq!(
+		calculator.eval("cross([1 0 0], [0 1 0])").unwrap(),
+		Some(Value::Vector(vec![0., 0., 1.]))
+	);
+	assert_eq!(
+		calculator.eval("lerp([0 0], [10 20], 0.25)").unwrap(),
+		Some(Value::Vector(vec![2.5, 5.]))
+	);
+	assert_eq!(calculator.eval("  ").unwrap(), None);
+
+	let error = calculator.eval("inv([1 2; 2 4])").unwrap_err();
+	assert_eq!(
+		error.to_string(),
+		"column 1: matrix is singular, it has no inverse"
+	);
+	let error = calculator.eval("a * [1 2 3]").unwrap_err();
+	assert_eq!(error.column, Some(3));
+	let error = calculator.eval("[1 2; 3] + 1").unwrap_err();
+	assert_eq!(error.to_string(), "column 7: row has 1 values, expected 2");
+	assert!(calculator.eval("b + 1").is_err());
+	assert!(calculator.eval("det(a, a)").is_err());
+	assert!(calculator.eval("det = 1").is_err());
+	assert!(calculator.eval("(1 + 2").is_err());
+	assert!(calculator.eval("1 2").is_err());
+
+	assert_eq!(calculator.history().len(), 19);
+	assert_eq!(calculator.history()[0], "a = [2 0; 0 4]");
+	assert_eq!(calculator.variables().len(), 2);
+
+	let mut calculator = Calculator::new();
+
+	calculator.set("m", Value::Vector(vec![1., 2.])).unwrap();
+	assert_eq!(calculator.get("m"), Some(&Value::Vector(vec![1., 2.])));
+	assert_eq!(
+		calculator.eval("solve([2 0; 0 4], m)").unwrap(),
+		Some(Value::Vector(vec![0.5, 0.5]))
+	);
+	let error = calculator.eval("solve([1 2; 2 4], m)").unwrap_err();
+	assert_eq!(error.kind, ErrorKind::Singular);
+	let error = calculator.eval("solve([1 2; 2 4], [1 2 3])").unwrap_err();
+	assert_eq!(error.kind, ErrorKind::Invalid);
+
+	// dot and matrix times vector round like Vector::dot and mul_vec
+
+	let x: [f32; 37] = std::array::from_fn(|i| (i as f32 * 0.37).sin());
+	let y: [f32; 37] = std::array::from_fn(|i| (i as f32 * 0.11).cos());
+	let text = |values: &[f32]| {
+		values
+			.iter()
+			.map(|x| x.to_string())
+			.collect::<Vec<_>>()
+			.join(" ")
+	};
+	let expected = Vector::new(x).dot(Vector::new(y));
+	let line = format!("dot([{}], [{}])", text(&x), text(&y));
+	assert_eq!(
+		calculator.eval(&line).unwrap(),
+		Some(Value::Scalar(expected))
+	);
+	let line = format!("[{}; {}] * [{}]", text(&x), text(&y), text(&y));
+	let image = Matrix::new([x, y]).mul_vec(Vector::new(y));
+	assert_eq!(
+		calculator.eval(&line).unwrap(),
+		Some(Value::Vector(vec![image.data[0][0], image.data[1][0]]))
+	);
+
+	// The identity appended by inv does not set the scale of A
+
+	let Some(Value::Matrix { data, .. }) = calculator.eval("inv([1e-8 0; 0 1e-8])").unwrap() else {
+		panic!("inv did not return a matrix");
+	};
+	assert_eq!(data, vec![1e8, 0., 0., 1e8]);
+
+	// Empty and malformed values set from outside
+
+	let empty = Value::Matrix {
+		rows: 0,
+		cols: 0,
+		data: vec![],
+	};
+	let flat = Value::Matrix {
+		rows: 3,
+		cols: 0,
+		data: vec![],
+	};
+	calculator.set("e", empty).unwrap();
+	calculator.set("f", flat).unwrap();
+	calculator.set("z", Value::Vector(vec![])).unwrap();
+	for line in [
+		"det(e)",
+		"inv(e)",
+		"rank(f)",
+		"rref(f)",
+		"trace(e)",
+		"solve(f, z)",
+		"dot(z, z)",
+	] {
+		let error = calculator.eval(line).unwrap_err();
+		assert!(error.message.contains("non-empty"), "{}", line);
+	}
+	assert_eq!(
+		calculator.eval("transpose(f)").unwrap().unwrap().kind(),
+		"0x3 matrix"
+	);
+	assert_eq!(calculator.get("f").unwrap().to_string(), "┌ ┐\n└ ┘\n");
+
+	let ragged = Value::Matrix {
+		rows: 2,
+		cols: 2,
+		data: vec![1., 2., 3.],
+	};
+	let error = calculator.set("r", ragged).unwrap_err();
+	assert_eq!(error.message, "a 2x2 matrix needs 4 values, got 3");
+}
+
+#[test]
+fn test_cli() {
+	use crate::cli::{self, Format};
+	use crate::npy::{write_npy, NpyArray};
+
+	let dir = std::env::temp_dir().join(format!("matrix-cli-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+	let write = |name: &str, bytes: &[u8]| std::fs::write(path(name), bytes).unwrap();
+	let run = |line: &str| {
+		let args: Vec<String> = line
+			.split(' ')
+			.map(|arg| match arg.contains('.') && !arg.starts_with('-') {
+				true => path(arg),
+				false => arg.to_string(),
+			})
+			.collect();
+		cli::report(&args)
+	};
+
+	assert!(cli::USAGE.starts_with("Usage: matrix"));
+	assert!(run("help").stdout.starts_with("Usage: matrix"));
+
+	// Detection: magic bytes, then the extension, then the text
+
+	assert_eq!(cli::detect("a", b"\x93NUMPY\x01\x00"), Format::Npy);
+	assert_eq!(cli::detect("a.csv", b"MTRX\x01"), Format::Binary);
+	assert_eq!(
+		cli::detect("a", b"%%MatrixMarket matrix"),
+		Format::MatrixMarket
+	);
+	assert_eq!(cli::detect("a.csv", b"1;2\n3;4\n"), Format::Csv(';'));
+	assert_eq!(cli::detect("a.tsv", b"1 2\n"), Format::Csv('\t'));
+	assert_eq!(cli::detect("a", b"1,2\n3,4\n"), Format::Csv(','));
+	assert_eq!(cli::detect("a", b"[1, 2]\n"), Format::Text);
+	assert_eq!(cli::detect("a.txt", b"1 2\n3 4\n"), Format::Text);
+
+	let args: Vec<String> = ["inv", "a.csv", "-o", "b.mtx", "--json", "--format", "csv"]
+		.iter()
+		.map(|arg| arg.to_string())
+		.collect();
+	let options = cli::parse_args(&args).unwrap();
+	assert_eq!(options.command, "inv");
+	assert_eq!(options.files, vec!["a.csv"]);
+	assert_eq!(options.output.as_deref(), Some("b.mtx"));
+	assert_eq!(options.format, Some(Format::Csv(',')));
+	assert!(options.json);
+	for line in ["det -o", "det --format", "det --format xls", "det --fast"] {
+		let args: Vec<String> = line.split(' ').map(String::from).collect();
+		assert_eq!(cli::parse_args(&args).unwrap_err().code, cli::USAGE_ERROR);
+	}
+
+	write("a.csv", b"x,y\n2,0\n0,4\n");
+	write("b.csv", b"1\n2\n");
+	write("s.txt", b"1 2\n2 4\n");
+
+	let report = run("det a.csv");
+	assert_eq!((report.stdout.as_str(), report.code), ("8\n", 0));
+	let report = run("solve a.csv b.csv --json");
+	assert_eq!(
+		report.stdout,
+		"{\"command\": \"solve\", \"result\": [0.5, 0.5]}\n"
+	);
+	let report = run("transpose a.csv --json");
+	assert_eq!(
+		report.stdout,
+		"{\"command\": \"transpose\", \"result\": [[2, 0], [0, 4]]}\n"
+	);
+
+	let report = run("inv s.txt --json");
+	assert_eq!(report.code, cli::SINGULAR_ERROR);
+	assert_eq!(
+		report.stdout,
+		"{\"command\": \"inv\", \"error\": \"matrix is singular, it has no inverse\", \"code\": 3}\n"
+	);
+	assert_eq!(run("solve s.txt b.csv").code, cli::SINGULAR_ERROR);
+	assert_eq!(run("det missing.csv").code, cli::INPUT_ERROR);
+	assert_eq!(run("det a.csv b.csv").code, cli::USAGE_ERROR);
+	let report = run("frobnicate a.csv");
+	assert_eq!(report.code, cli::USAGE_ERROR);
+	assert!(report.stderr.ends_with("Run 'matrix help' for usage\n"));
+
+	// Results written with -o are read back in the format of the extension
+
+	for output in ["i.mtx", "i.npy", "i.bin", "i.csv", "i.txt"] {
+		let report = run(&format!("inv a.csv -o {}", output));
+		assert_eq!((report.stdout.as_str(), report.code), ("", 0));
+		let report = run(&format!("transpose {} --json", output));
+		assert_eq!(
+			report.stdout, "{\"command\": \"transpose\", \"result\": [[0.5, 0], [0, 0.25]]}\n",
+			"{}",
+			output
+		);
+	}
+	assert_eq!(run("det a.csv -o d.csv").code, cli::USAGE_ERROR);
+
+	// Degenerate and hostile inputs are invalid input, not crashes
+
+	let mut bytes = Vec::new();
+	let empty = NpyArray::<f32> {
+		shape: vec![0, 0],
+		data: vec![],
+	};
+	write_npy(&mut bytes, &empty, &NpyOptions::default()).unwrap();
+	write("zz.npy", &bytes);
+	let mut bytes = Vec::new();
+	let empty = NpyArray::<f32> {
+		shape: vec![0],
+		data: vec![],
+	};
+	write_npy(&mut bytes, &empty, &NpyOptions::default()).unwrap();
+	write("e.npy", &bytes);
+	write(
+		"z.mtx",
+		b"%%MatrixMarket matrix coordinate real general\n3 0 0\n",
+	);
+	write(
+		"h.mtx",
+		b"%%MatrixMarket matrix coordinate real general\n100000 100000 0\n",
+	);
+
+	for line in [
+		"transpose zz.npy --json",
+		"transpose e.npy --json",
+		"det zz.npy",
+		"rank z.mtx",
+	] {
+		assert_eq!(run(line).code, cli::INPUT_ERROR, "{}", line);
+	}
+	let report = run("rank h.mtx");
+	assert_eq!(report.code, cli::INPUT_ERROR);
+	assert!(report.stderr.contains("100000x100000 is too large"));
+	write(
+		"a.mtx",
+		b"%%MatrixMarket matrix array real general\n200000 200000\n1\n",
+	);
+	let report = run("det a.mtx");
+	assert_eq!(report.code, cli::INPUT_ERROR);
+	assert!(report.stderr.contains("200000x200000 array is too large"));
+
+	// A trailing delimiter is not a header
+
+	write("t.csv", b"1,2,\n3,4,\n");
+	let report = run("det t.csv");
+	assert_eq!(report.code, cli::INPUT_ERROR);
+	assert!(report.stderr.contains("line 1"), "{}", report.stderr);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
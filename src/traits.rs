@@ -6,17 +6,35 @@
 /*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
 /*                                                +#+#+#+#+#+   +#+           */
 /*   Created: 2024/12/24 16:58:30 by ggalon            #+#    #+#             */
-/*   Updated: 2026/01/25 15:47:06 by ggalon           ###   ########.fr       */
+/*   Updated: 2026/10/19 03:41:24 by ggalon           ###   ########.fr       */
 /*                                                                            */
 /* ************************************************************************** */
 
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use crate::simd;
+
 pub trait MulAdd<A = Self, B = Self> {
 	type Output;
 
 	fn mul_add(self, a: A, b: B) -> Self::Output;
+
+	// y[i] = x[i] * alpha + y[i] in index order. f32 and f64 override it with
+	// the SIMD kernels, which give the same bits
+
+	fn axpy(alpha: Self, x: &[Self], y: &mut [Self])
+	where
+		Self: Copy + MulAdd<Output = Self>,
+	{
+		if x.len() != y.len() {
+			panic!("Error: Arrays sizes are different");
+		}
+
+		for i in 0..x.len() {
+			y[i] = x[i].mul_add(alpha, y[i]);
+		}
+	}
 }
 
 impl MulAdd for f32 {
@@ -25,10 +43,28 @@ impl MulAdd for f32 {
 	fn mul_add(self, a: f32, b: f32) -> Self::Output {
 		f32::mul_add(self, a, b)
 	}
+
+	fn axpy(alpha: f32, x: &[f32], y: &mut [f32]) {
+		simd::axpy_f32(alpha, x, y);
+	}
+}
+
+impl MulAdd for f64 {
+	type Output = f64;
+
+	fn mul_add(self, a: f64, b: f64) -> Self::Output {
+		f64::mul_add(self, a, b)
+	}
+
+	fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+		simd::axpy_f64(alpha, x, y);
+	}
 }
 
 pub trait Traits:
 	Debug
+	+ Send
+	+ Sync
 	+ Default
 	+ Copy
 	+ From<f32>
@@ -50,6 +86,8 @@ pub trait Traits:
 
 impl<V> Traits for V where
 	V: Debug
+		+ Send
+		+ Sync
 		+ Default
 		+ Copy
 		+ From<f32>
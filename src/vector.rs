@@ -6,7 +6,7 @@
 /*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
 /*                                                +#+#+#+#+#+   +#+           */
 /*   Created: 2024/12/24 13:13:02 by ggalon            #+#    #+#             */
-/*   Updated: 2026/01/25 15:47:10 by ggalon           ###   ########.fr       */
+/*   Updated: 2026/10/19 03:41:24 by ggalon           ###   ########.fr       */
 /*                                                                            */
 /* ************************************************************************** */
 
@@ -15,6 +15,7 @@ use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 use crate::matrix::Matrix;
 use crate::traits::Traits;
 
+#[derive(Debug, PartialEq)]
 pub struct Vector<K, const N: usize> {
 	pub data: [K; N],
 	pub size: usize,
@@ -26,10 +27,6 @@ impl<K: Traits, const N: usize> Vector<K, N> {
 		Self { data, size }
 	}
 
-	pub fn clone(&self) -> Self {
-		return Self::new(self.data);
-	}
-
 	#[allow(dead_code)]
 	pub fn vtom(&self) -> Matrix<K, N, 1> {
 		let mut matrix = [[K::default(); 1]; N];
@@ -72,9 +69,7 @@ impl<K: Traits, const N: usize> Vector<K, N> {
 		let mut result = Vector::new([K::default(); N]);
 
 		for i in 0..u.len() {
-			for j in 0..N {
-				result.data[j] = u[i].data[j].mul_add(coefs[i], result.data[j]);
-			}
+			K::axpy(coefs[i], &u[i].data, &mut result.data);
 		}
 
 		return result;
@@ -144,6 +139,108 @@ impl<K: Traits, const N: usize> Vector<K, N> {
 			u.data[0].mul_add(v.data[1], -(u.data[1] * v.data[0])),
 		])
 	}
+
+	pub fn map<L: Traits, F: FnMut(K) -> L>(&self, mut f: F) -> Vector<L, N> {
+		let mut result = Vector::new([L::default(); N]);
+
+		for i in 0..N {
+			result.data[i] = f(self.data[i]);
+		}
+
+		return result;
+	}
+
+	pub fn zip_map<L: Traits, F: FnMut(K, K) -> L>(
+		&self,
+		v: &Vector<K, N>,
+		mut f: F,
+	) -> Vector<L, N> {
+		let mut result = Vector::new([L::default(); N]);
+
+		for i in 0..N {
+			result.data[i] = f(self.data[i], v.data[i]);
+		}
+
+		return result;
+	}
+
+	pub fn apply<F: FnMut(K) -> K>(&mut self, mut f: F) {
+		for i in 0..N {
+			self.data[i] = f(self.data[i]);
+		}
+	}
+
+	pub fn fold<A, F: FnMut(A, K) -> A>(&self, init: A, mut f: F) -> A {
+		let mut result = init;
+
+		for i in 0..N {
+			result = f(result, self.data[i]);
+		}
+
+		return result;
+	}
+
+	pub fn sum(&self) -> K {
+		return self.fold(K::default(), |acc, x| acc + x);
+	}
+
+	pub fn product(&self) -> K {
+		return self.fold(K::from(1.), |acc, x| acc * x);
+	}
+
+	pub fn argmin(&self) -> Option<usize> {
+		if N == 0 {
+			return None;
+		}
+
+		let mut result = 0;
+
+		for i in 1..N {
+			if self.data[i] < self.data[result] {
+				result = i;
+			}
+		}
+
+		return Some(result);
+	}
+
+	pub fn argmax(&self) -> Option<usize> {
+		if N == 0 {
+			return None;
+		}
+
+		let mut result = 0;
+
+		for i in 1..N {
+			if self.data[i] > self.data[result] {
+				result = i;
+			}
+		}
+
+		return Some(result);
+	}
+
+	pub fn min(&self) -> Option<K> {
+		return self.argmin().map(|i| self.data[i]);
+	}
+
+	pub fn max(&self) -> Option<K> {
+		return self.argmax().map(|i| self.data[i]);
+	}
+
+	pub fn any<F: FnMut(K) -> bool>(&self, f: F) -> bool {
+		return self.data.iter().copied().any(f);
+	}
+
+	pub fn all<F: FnMut(K) -> bool>(&self, f: F) -> bool {
+		return self.data.iter().copied().all(f);
+	}
+}
+
+impl<K: Traits, const N: usize> Clone for Vector<K, N> {
+	fn clone(&self) -> Self {
+		return Self::new(self.data);
+	}
 }
 
 impl<K: Traits, const N: usize> Add for Vector<K, N> {
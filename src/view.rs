@@ -0,0 +1,288 @@
+/* ************************************************************************** */
+/*                                                                            */
+/*                                                        :::      ::::::::   */
+/*   view.rs                                            :+:      :+:    :+:   */
+/*                                                    +:+ +:+         +:+     */
+/*   By: ggalon <ggalon@student.42.fr>              +#+  +:+       +#+        */
+/*                                                +#+#+#+#+#+   +#+           */
+/*   Created: 2026/10/19 03:03:32 by ggalon            #+#    #+#             */
+/*   Updated: 2026/10/19 03:05:57 by ggalon           ###   ########.fr       */
+/*                                                                            */
+/* ************************************************************************** */
+
+use crate::matrix::Matrix;
+use crate::traits::Traits;
+
+// Borrowed windows into the row storage of a Matrix. A block keeps the
+// slice of its rows plus the range of columns it covers, so nothing is
+// copied until to_matrix is called.
+
+pub struct BlockView<'a, K, const N: usize> {
+	data: &'a [[K; N]],
+	col_start: usize,
+	cols: usize,
+}
+
+pub struct BlockViewMut<'a, K, const N: usize> {
+	data: &'a mut [[K; N]],
+	col_start: usize,
+	cols: usize,
+}
+
+pub struct ColumnView<'a, K, const N: usize> {
+	data: &'a [[K; N]],
+	col: usize,
+}
+
+pub struct ColumnViewMut<'a, K, const N: usize> {
+	data: &'a mut [[K; N]],
+	col: usize,
+}
+
+fn check_block(row: usize, col: usize, rows: usize, cols: usize, max_rows: usize, max_cols: usize) {
+	if row + rows > max_rows || col + cols > max_cols {
+		panic!("Error: Block is out of the matrix bounds");
+	}
+}
+
+impl<'a, K: Traits, const N: usize> BlockView<'a, K, N> {
+	pub fn new(data: &'a [[K; N]], row: usize, col: usize, rows: usize, cols: usize) -> Self {
+		check_block(row, col, rows, cols, data.len(), N);
+
+		Self {
+			data: &data[row..row + rows],
+			col_start: col,
+			cols,
+		}
+	}
+
+	pub fn rows(&self) -> usize {
+		return self.data.len();
+	}
+
+	pub fn cols(&self) -> usize {
+		return self.cols;
+	}
+
+	pub fn get(&self, i: usize, j: usize) -> K {
+		return self.row(i)[j];
+	}
+
+	pub fn row(&self, i: usize) -> &'a [K] {
+		return &self.data[i][self.col_start..self.col_start + self.cols];
+	}
+
+	pub fn column(&self, j: usize) -> ColumnView<'a, K, N> {
+		if j >= self.cols {
+			panic!("Error: Column is out of the block bounds");
+		}
+
+		ColumnView {
+			data: self.data,
+			col: self.col_start + j,
+		}
+	}
+
+	pub fn iter_rows(&self) -> impl Iterator<Item = &'a [K]> + '_ {
+		return (0..self.rows()).map(|i| self.row(i));
+	}
+
+	pub fn iter_columns(&self) -> impl Iterator<Item = ColumnView<'a, K, N>> + '_ {
+		return (0..self.cols).map(|j| self.column(j));
+	}
+
+	pub fn block(&self, row: usize, col: usize, rows: usize, cols: usize) -> BlockView<'a, K, N> {
+		check_block(row, col, rows, cols, self.rows(), self.cols);
+
+		BlockView {
+			data: &self.data[row..row + rows],
+			col_start: self.col_start + col,
+			cols,
+		}
+	}
+
+	pub fn to_matrix<const R: usize, const C: usize>(&self) -> Matrix<K, R, C> {
+		if R != self.rows() || C != self.cols {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		let mut result: Matrix<K, R, C> = Matrix::zeros();
+
+		for i in 0..R {
+			result.data[i].copy_from_slice(self.row(i));
+		}
+
+		return result;
+	}
+}
+
+impl<'a, K: Traits, const N: usize> BlockViewMut<'a, K, N> {
+	pub fn new(data: &'a mut [[K; N]], row: usize, col: usize, rows: usize, cols: usize) -> Self {
+		check_block(row, col, rows, cols, data.len(), N);
+
+		Self {
+			data: &mut data[row..row + rows],
+			col_start: col,
+			cols,
+		}
+	}
+
+	pub fn rows(&self) -> usize {
+		return self.data.len();
+	}
+
+	pub fn cols(&self) -> usize {
+		return self.cols;
+	}
+
+	pub fn as_view(&self) -> BlockView<'_, K, N> {
+		BlockView {
+			data: self.data,
+			col_start: self.col_start,
+			cols: self.cols,
+		}
+	}
+
+	pub fn get(&self, i: usize, j: usize) -> K {
+		return self.row(i)[j];
+	}
+
+	pub fn get_mut(&mut self, i: usize, j: usize) -> &mut K {
+		return &mut self.row_mut(i)[j];
+	}
+
+	pub fn set(&mut self, i: usize, j: usize, value: K) {
+		*self.get_mut(i, j) = value;
+	}
+
+	pub fn row(&self, i: usize) -> &[K] {
+		return &self.data[i][self.col_start..self.col_start + self.cols];
+	}
+
+	pub fn row_mut(&mut self, i: usize) -> &mut [K] {
+		return &mut self.data[i][self.col_start..self.col_start + self.cols];
+	}
+
+	pub fn column_mut(&mut self, j: usize) -> ColumnViewMut<'_, K, N> {
+		if j >= self.cols {
+			panic!("Error: Column is out of the block bounds");
+		}
+
+		ColumnViewMut {
+			data: self.data,
+			col: self.col_start + j,
+		}
+	}
+
+	pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = &mut [K]> + '_ {
+		let range = self.col_start..self.col_start + self.cols;
+
+		return self.data.iter_mut().map(move |row| &mut row[range.clone()]);
+	}
+
+	pub fn block_mut(
+		&mut self,
+		row: usize,
+		col: usize,
+		rows: usize,
+		cols: usize,
+	) -> BlockViewMut<'_, K, N> {
+		check_block(row, col, rows, cols, self.rows(), self.cols);
+
+		BlockViewMut {
+			data: &mut self.data[row..row + rows],
+			col_start: self.col_start + col,
+			cols,
+		}
+	}
+
+	pub fn fill(&mut self, value: K) {
+		for row in self.iter_rows_mut() {
+			row.fill(value);
+		}
+	}
+
+	pub fn assign<const R: usize, const C: usize>(&mut self, matrix: &Matrix<K, R, C>) {
+		if R != self.rows() || C != self.cols {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		for (row, source) in self.iter_rows_mut().zip(matrix.data.iter()) {
+			row.copy_from_slice(source);
+		}
+	}
+
+	pub fn assign_view<const P: usize>(&mut self, view: &BlockView<K, P>) {
+		if view.rows() != self.rows() || view.cols() != self.cols {
+			panic!("Error: Matrix sizes are different");
+		}
+
+		for (i, row) in self.iter_rows_mut().enumerate() {
+			row.copy_from_slice(view.row(i));
+		}
+	}
+}
+
+impl<'a, K: Traits, const N: usize> ColumnView<'a, K, N> {
+	pub fn new(data: &'a [[K; N]], col: usize) -> Self {
+		if col >= N {
+			panic!("Error: Column is out of the matrix bounds");
+		}
+
+		Self { data, col }
+	}
+
+	pub fn len(&self) -> usize {
+		return self.data.len();
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.data.is_empty();
+	}
+
+	pub fn get(&self, i: usize) -> K {
+		return self.data[i][self.col];
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = K> + 'a {
+		let col = self.col;
+
+		return self.data.iter().map(move |row| row[col]);
+	}
+}
+
+impl<'a, K: Traits, const N: usize> ColumnViewMut<'a, K, N> {
+	pub fn new(data: &'a mut [[K; N]], col: usize) -> Self {
+		if col >= N {
+			panic!("Error: Column is out of the matrix bounds");
+		}
+
+		Self { data, col }
+	}
+
+	pub fn len(&self) -> usize {
+		return self.data.len();
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.data.is_empty();
+	}
+
+	pub fn get(&self, i: usize) -> K {
+		return self.data[i][self.col];
+	}
+
+	pub fn get_mut(&mut self, i: usize) -> &mut K {
+		return &mut self.data[i][self.col];
+	}
+
+	pub fn set(&mut self, i: usize, value: K) {
+		self.data[i][self.col] = value;
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut K> + '_ {
+		let col = self.col;
+
+		return self.data.iter_mut().map(move |row| &mut row[col]);
+	}
+}